@@ -0,0 +1,274 @@
+//! A small self-contained arbitrary-precision integer used by
+//! [`Number::BigInt`](crate::value::Number::BigInt).
+//!
+//! The representation is a sign plus a little-endian `Vec<u64>` magnitude (least
+//! significant limb first) with no trailing zero limbs, so equality and display
+//! are canonical. It supports the operations the integer arithmetic intrinsics
+//! need — addition, subtraction, multiplication — along with decimal/hex parsing
+//! and lossless conversion to and from `i64` when the value is in range.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt {
+	/// `true` when the value is strictly negative. Zero is always non-negative.
+	negative: bool,
+	/// Little-endian magnitude with no trailing zero limbs (empty == zero).
+	mag: Vec<u64>,
+}
+
+impl BigInt {
+	/// The additive identity.
+	pub fn zero() -> Self {
+		BigInt { negative: false, mag: Vec::new() }
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.mag.is_empty()
+	}
+
+	/// Builds a `BigInt` from an `i64`.
+	pub fn from_i64(value: i64) -> Self {
+		if value == 0 {
+			return BigInt::zero();
+		}
+		let negative = value < 0;
+		// `-i64::MIN` overflows, so widen through u128 before taking the magnitude.
+		let mag_val = (value as i128).unsigned_abs() as u128;
+		BigInt { negative, mag: vec![mag_val as u64] }.normalized()
+	}
+
+	/// Returns the value as an `i64` when it fits, otherwise `None`.
+	pub fn to_i64(&self) -> Option<i64> {
+		if self.mag.len() > 1 {
+			return None;
+		}
+		let limb = *self.mag.first().unwrap_or(&0);
+		if self.negative {
+			if limb <= (i64::MAX as u64) + 1 {
+				Some((limb as i128 * -1) as i64)
+			} else {
+				None
+			}
+		} else if limb <= i64::MAX as u64 {
+			Some(limb as i64)
+		} else {
+			None
+		}
+	}
+
+	pub fn fits_i64(&self) -> bool {
+		self.to_i64().is_some()
+	}
+
+	/// Parses a decimal (optionally signed) integer.
+	pub fn from_decimal(s: &str) -> Option<Self> {
+		let (negative, digits) = match s.strip_prefix('-') {
+			Some(rest) => (true, rest),
+			None => (false, s.strip_prefix('+').unwrap_or(s)),
+		};
+		if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+			return None;
+		}
+		let mut acc = BigInt::zero();
+		let ten = BigInt::from_i64(10);
+		for b in digits.bytes() {
+			acc = acc.mul(&ten).add(&BigInt::from_i64((b - b'0') as i64));
+		}
+		acc.negative = negative && !acc.is_zero();
+		Some(acc.normalized())
+	}
+
+	/// Parses a hexadecimal magnitude (no `0x` prefix, no sign).
+	pub fn from_hex(s: &str) -> Option<Self> {
+		if s.is_empty() {
+			return None;
+		}
+		let mut acc = BigInt::zero();
+		let sixteen = BigInt::from_i64(16);
+		for b in s.bytes() {
+			let digit = (b as char).to_digit(16)? as i64;
+			acc = acc.mul(&sixteen).add(&BigInt::from_i64(digit));
+		}
+		Some(acc.normalized())
+	}
+
+	pub fn add(&self, other: &BigInt) -> BigInt {
+		if self.negative == other.negative {
+			BigInt { negative: self.negative, mag: add_mag(&self.mag, &other.mag) }.normalized()
+		} else {
+			match cmp_mag(&self.mag, &other.mag) {
+				Ordering::Equal => BigInt::zero(),
+				Ordering::Greater => {
+					BigInt { negative: self.negative, mag: sub_mag(&self.mag, &other.mag) }.normalized()
+				}
+				Ordering::Less => {
+					BigInt { negative: other.negative, mag: sub_mag(&other.mag, &self.mag) }.normalized()
+				}
+			}
+		}
+	}
+
+	pub fn sub(&self, other: &BigInt) -> BigInt {
+		self.add(&other.neg())
+	}
+
+	pub fn mul(&self, other: &BigInt) -> BigInt {
+		if self.is_zero() || other.is_zero() {
+			return BigInt::zero();
+		}
+		BigInt {
+			negative: self.negative != other.negative,
+			mag: mul_mag(&self.mag, &other.mag),
+		}
+		.normalized()
+	}
+
+	pub fn neg(&self) -> BigInt {
+		if self.is_zero() {
+			BigInt::zero()
+		} else {
+			BigInt { negative: !self.negative, mag: self.mag.clone() }
+		}
+	}
+
+	/// The absolute value.
+	pub fn abs(&self) -> BigInt {
+		BigInt { negative: false, mag: self.mag.clone() }
+	}
+
+	/// `true` when the value is strictly negative.
+	pub fn is_negative(&self) -> bool {
+		self.negative
+	}
+
+	/// Total order over arbitrary-precision values: sign first, then
+	/// magnitude (most-significant limb first) once both sides agree.
+	pub fn cmp(&self, other: &BigInt) -> Ordering {
+		match (self.negative, other.negative) {
+			(false, true) => Ordering::Greater,
+			(true, false) => Ordering::Less,
+			(false, false) => cmp_mag(&self.mag, &other.mag),
+			(true, true) => cmp_mag(&other.mag, &self.mag),
+		}
+	}
+
+	/// Approximates the value as `f64` (used by int→float conversion).
+	pub fn to_f64(&self) -> f64 {
+		let mut acc = 0.0f64;
+		for limb in self.mag.iter().rev() {
+			acc = acc * (u64::MAX as f64 + 1.0) + *limb as f64;
+		}
+		if self.negative {
+			-acc
+		} else {
+			acc
+		}
+	}
+
+	fn normalized(mut self) -> Self {
+		while self.mag.last() == Some(&0) {
+			self.mag.pop();
+		}
+		if self.mag.is_empty() {
+			self.negative = false;
+		}
+		self
+	}
+}
+
+impl fmt::Display for BigInt {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
+		// Repeatedly divide the magnitude by 1e18 to build decimal chunks.
+		let mut limbs = self.mag.clone();
+		let mut chunks: Vec<u64> = Vec::new();
+		while !limbs.is_empty() {
+			let mut rem: u128 = 0;
+			for limb in limbs.iter_mut().rev() {
+				let cur = (rem << 64) | *limb as u128;
+				*limb = (cur / 1_000_000_000_000_000_000u128) as u64;
+				rem = cur % 1_000_000_000_000_000_000u128;
+			}
+			while limbs.last() == Some(&0) {
+				limbs.pop();
+			}
+			chunks.push(rem as u64);
+		}
+		if self.negative {
+			write!(f, "-")?;
+		}
+		if let Some(most) = chunks.pop() {
+			write!(f, "{}", most)?;
+		}
+		for chunk in chunks.iter().rev() {
+			write!(f, "{:018}", chunk)?;
+		}
+		Ok(())
+	}
+}
+
+fn cmp_mag(a: &[u64], b: &[u64]) -> Ordering {
+	if a.len() != b.len() {
+		return a.len().cmp(&b.len());
+	}
+	for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+		match x.cmp(y) {
+			Ordering::Equal => continue,
+			non_eq => return non_eq,
+		}
+	}
+	Ordering::Equal
+}
+
+fn add_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+	let mut out = Vec::with_capacity(a.len().max(b.len()) + 1);
+	let mut carry = 0u128;
+	for i in 0..a.len().max(b.len()) {
+		let x = *a.get(i).unwrap_or(&0) as u128;
+		let y = *b.get(i).unwrap_or(&0) as u128;
+		let sum = x + y + carry;
+		out.push(sum as u64);
+		carry = sum >> 64;
+	}
+	if carry != 0 {
+		out.push(carry as u64);
+	}
+	out
+}
+
+/// Requires `a >= b`.
+fn sub_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+	let mut out = Vec::with_capacity(a.len());
+	let mut borrow = 0i128;
+	for i in 0..a.len() {
+		let x = a[i] as i128;
+		let y = *b.get(i).unwrap_or(&0) as i128;
+		let mut diff = x - y - borrow;
+		if diff < 0 {
+			diff += 1i128 << 64;
+			borrow = 1;
+		} else {
+			borrow = 0;
+		}
+		out.push(diff as u64);
+	}
+	out
+}
+
+fn mul_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+	let mut out = vec![0u64; a.len() + b.len()];
+	for (i, &x) in a.iter().enumerate() {
+		let mut carry = 0u128;
+		for (j, &y) in b.iter().enumerate() {
+			let cur = out[i + j] as u128 + x as u128 * y as u128 + carry;
+			out[i + j] = cur as u64;
+			carry = cur >> 64;
+		}
+		out[i + b.len()] += carry as u64;
+	}
+	out
+}
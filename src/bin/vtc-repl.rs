@@ -0,0 +1,211 @@
+//! Interactive REPL over [`vtc::runtime::Runtime`].
+//!
+//! ```text
+//! vtc-repl
+//! >>> @app:
+//! ...     $host := "localhost"
+//! ...     $port := 8080
+//! ...
+//! >>> %app.host
+//! "localhost"
+//! >>> :list
+//! app
+//! >>> :dump app
+//! @app:
+//!     $host := "localhost"
+//!     $port := 8080
+//! >>> :quit
+//! ```
+//!
+//! Borrows schala's multi-line handling: a line that leaves brackets unbalanced,
+//! or ends mid `:=` assignment, is buffered and re-prompted with `...` until the
+//! statement is syntactically complete, so a whole `@ns: $v := [ ... ]` block can
+//! be pasted in one go. Parse/tokenize failures are printed inline and do not end
+//! the session.
+
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+
+use vtc::parser::ast::to_vtc_string;
+use vtc::runtime::Runtime;
+use vtc::value::{Namespace, Variable, VtcFile};
+
+fn main() {
+	let stdin = io::stdin();
+	let mut lines = stdin.lock().lines();
+	let mut runtime = Runtime::new();
+	let mut buffer = String::new();
+
+	loop {
+		print_prompt(&buffer);
+
+		let line = match lines.next() {
+			Some(Ok(line)) => line,
+			Some(Err(e)) => {
+				eprintln!("input error: {}", e);
+				break;
+			}
+			None => break,
+		};
+
+		if buffer.is_empty() {
+			let trimmed = line.trim();
+			if trimmed.is_empty() {
+				continue;
+			}
+			if let Some(command) = trimmed.strip_prefix(':') {
+				if command == "quit" || command == "exit" {
+					break;
+				}
+				run_command(&mut runtime, command);
+				continue;
+			}
+		}
+
+		buffer.push_str(&line);
+		buffer.push('\n');
+
+		if !is_complete(&buffer) {
+			continue;
+		}
+
+		let statement = std::mem::take(&mut buffer);
+		run_statement(&mut runtime, &statement);
+	}
+}
+
+fn print_prompt(buffer: &str) {
+	print!("{}", if buffer.is_empty() { ">>> " } else { "... " });
+	let _ = io::stdout().flush();
+}
+
+/// Runs one complete, buffered statement: a `@namespace: ...` definition is
+/// loaded into the runtime, anything else is treated as a value expression
+/// (typically a `%ns.var->(accessor)` reference) and evaluated against it.
+fn run_statement(runtime: &mut Runtime, statement: &str) {
+	if statement.trim_start().starts_with('@') {
+		if let Err(e) = runtime.load_vtc(statement) {
+			eprintln!("error: {}", e);
+		}
+		return;
+	}
+
+	match evaluate_expression(runtime, statement.trim()) {
+		Ok(value) => println!("{}", value),
+		Err(e) => eprintln!("error: {}", e),
+	}
+}
+
+/// Evaluates a bare expression by loading it into a scratch namespace and
+/// immediately reading it back, so the REPL gets the same reference
+/// resolution, accessors and intrinsics the runtime normally offers.
+fn evaluate_expression(runtime: &mut Runtime, expr: &str) -> Result<String, String> {
+	const SCRATCH_NAMESPACE: &str = "_repl";
+	const SCRATCH_VARIABLE: &str = "_";
+
+	let wrapped = format!("@{}:\n    ${} := {}\n", SCRATCH_NAMESPACE, SCRATCH_VARIABLE, expr);
+	runtime.load_vtc(&wrapped).map_err(|e| e.to_string())?;
+
+	let result = runtime
+		.get_value(SCRATCH_NAMESPACE, SCRATCH_VARIABLE, &[])
+		.map(|v| v.to_string())
+		.map_err(|e| e.to_string());
+
+	let _ = runtime.delete_namespace(SCRATCH_NAMESPACE);
+	result
+}
+
+fn run_command(runtime: &mut Runtime, command: &str) {
+	let mut parts = command.split_whitespace();
+	match parts.next() {
+		Some("help") => print_help(),
+		Some("list") => {
+			let mut names: Vec<String> = runtime.list_namespaces().iter().map(|n| n.to_string()).collect();
+			names.sort();
+			for name in names {
+				println!("{}", name);
+			}
+		}
+		Some("dump") => match parts.next() {
+			Some(namespace) => match dump_namespace(runtime, namespace) {
+				Some(text) => println!("{}", text),
+				None => eprintln!("error: namespace `{}` not found", namespace),
+			},
+			None => {
+				let mut names: Vec<String> = runtime.list_namespaces().iter().map(|n| n.to_string()).collect();
+				names.sort();
+				for name in names {
+					if let Some(text) = dump_namespace(runtime, &name) {
+						println!("{}", text);
+					}
+				}
+			}
+		},
+		Some("delete") => match (parts.next(), parts.next()) {
+			(Some(namespace), Some(variable)) => {
+				if let Err(e) = runtime.delete_value(namespace, variable) {
+					eprintln!("error: {}", e);
+				}
+			}
+			_ => eprintln!("usage: :delete <namespace> <variable>"),
+		},
+		Some("reset") => *runtime = Runtime::new(),
+		Some(other) => eprintln!("unknown command `:{}` (try `:help`)", other),
+		None => eprintln!("unknown command (try `:help`)"),
+	}
+}
+
+fn print_help() {
+	println!(":list               list every namespace");
+	println!(":dump [namespace]   print a namespace (or all of them) as VTC text");
+	println!(":delete <ns> <var>  remove a variable from a namespace");
+	println!(":reset              discard all namespaces and variables");
+	println!(":quit / :exit       leave the REPL");
+	println!("@ns:\\n    $v := ... define or replace a namespace");
+	println!("%ns.var->(0)        evaluate a reference expression");
+}
+
+/// Renders a namespace's *unresolved* bindings as VTC text, straight from the
+/// runtime's own storage rather than `get_value`, so references print as
+/// `%ns.var` instead of their resolved target.
+fn dump_namespace(runtime: &Runtime, namespace: &str) -> Option<String> {
+	let variables = runtime
+		.namespaces
+		.get(&Arc::new(namespace.to_string()))?
+		.iter()
+		.map(|(name, value)| Variable { name: name.to_string(), value: (**value).clone() })
+		.collect();
+
+	Some(to_vtc_string(&VtcFile {
+		namespaces: vec![Namespace { name: namespace.to_string(), variables }],
+	}))
+}
+
+/// A buffered statement is complete once every bracket the user opened has
+/// been closed, and:
+/// - for a `@namespace: ...` definition, a blank line marks the end of the
+///   block (it may declare any number of variables, each on its own line);
+/// - for anything else (a single value expression), simply not ending
+///   mid `:=` assignment is enough.
+fn is_complete(buffer: &str) -> bool {
+	if bracket_depth(buffer) != 0 {
+		return false;
+	}
+	if buffer.trim_start().starts_with('@') {
+		buffer.ends_with("\n\n")
+	} else {
+		!buffer.trim_end().ends_with(":=")
+	}
+}
+
+fn bracket_depth(buffer: &str) -> i32 {
+	let mut depth = 0;
+	for ch in buffer.chars() {
+		match ch {
+			'[' | '(' | '{' => depth += 1,
+			']' | ')' | '}' => depth -= 1,
+			_ => {}
+		}
+	}
+	depth
+}
@@ -0,0 +1,52 @@
+//! Reads a schema file and emits the generated Rust module to stdout (or a file).
+//!
+//! ```text
+//! vtc-schemagen schema.vtcs > src/config_schema.rs
+//! vtc-schemagen schema.vtcs src/config_schema.rs
+//! ```
+
+use std::process::ExitCode;
+
+use vtc::schema::{codegen, Schema};
+
+fn main() -> ExitCode {
+	let mut args = std::env::args().skip(1);
+	let input = match args.next() {
+		Some(path) => path,
+		None => {
+			eprintln!("usage: vtc-schemagen <schema-file> [output-file]");
+			return ExitCode::FAILURE;
+		}
+	};
+	let output = args.next();
+
+	let source = match std::fs::read_to_string(&input) {
+		Ok(source) => source,
+		Err(e) => {
+			eprintln!("failed to read `{}`: {}", input, e);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	let schema = match Schema::parse(&source) {
+		Ok(schema) => schema,
+		Err(e) => {
+			eprintln!("schema parse error: {}", e);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	let generated = codegen::generate_rust(&schema);
+
+	match output {
+		Some(path) => {
+			if let Err(e) = std::fs::write(&path, generated) {
+				eprintln!("failed to write `{}`: {}", path, e);
+				return ExitCode::FAILURE;
+			}
+		}
+		None => print!("{}", generated),
+	}
+
+	ExitCode::SUCCESS
+}
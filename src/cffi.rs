@@ -4,10 +4,22 @@ use std::os::raw::{c_char, c_double, c_int};
 use std::ptr;
 use std::rc::Rc;
 use std::slice;
+use std::sync::Arc;
 
+use crate::runtime::std::VtcFn;
 use crate::runtime::Runtime;
 use crate::value::{Number, Value};
 
+/// Signature of a C callback installed with [`runtime_register_function`].
+///
+/// The callback receives a borrowed array of `len` `*const Value` pointers that
+/// are only valid for the duration of the call, and must return a freshly heap
+/// allocated `*mut Value` (see [`value_new_integer`] and friends). Ownership of
+/// the returned pointer is *moved* into the runtime, which reclaims it
+/// internally — the host must not free it afterwards. Returning a null pointer
+/// yields `Value::Nil`.
+pub type VtcCallback = extern "C" fn(args: *const *const Value, len: usize) -> *mut Value;
+
 #[repr(C)]
 pub struct CRuntime(*mut Runtime);
 
@@ -53,6 +65,109 @@ pub extern "C" fn runtime_load_vtc(runtime: CRuntime, input: *const c_char) -> c
     }
 }
 
+/// Registers a host-provided evaluator under `name`, usable as `[name!!, ...]`
+/// inside loaded VTC files.
+///
+/// The callback is wrapped in a [`VtcFn`] that marshals the resolved arguments
+/// into borrowed `*const Value` pointers for the duration of the call and takes
+/// ownership of the returned `*mut Value` (boxed and reclaimed internally, so it
+/// is never double-freed). Returns 0 on success and -1 if the runtime pointer is
+/// null, the name is not valid UTF-8, or the name is reserved (`std`-prefixed).
+#[no_mangle]
+pub extern "C" fn runtime_register_function(
+    runtime: CRuntime,
+    name: *const c_char,
+    cb: VtcCallback,
+) -> c_int {
+    let runtime = match unsafe { runtime.0.as_mut() } {
+        Some(r) => r,
+        None => return -1,
+    };
+    let name = match unsafe { CStr::from_ptr(name) }.to_str() {
+        Ok(s) => s.to_string(),
+        Err(_) => return -1,
+    };
+
+    let function: VtcFn = Box::new(move |args: Vec<Arc<Value>>| {
+        // `args` pointers are borrowed for the duration of the call only.
+        let ptrs: Vec<*const Value> = args.iter().map(|a| Arc::as_ptr(a)).collect();
+        let ret = cb(ptrs.as_ptr(), ptrs.len());
+        if ret.is_null() {
+            Ok(Arc::new(Value::Nil))
+        } else {
+            // The callback moved ownership of `ret` to us; reclaim it here.
+            Ok(Arc::new(*unsafe { Box::from_raw(ret) }))
+        }
+    });
+
+    match runtime.register_function(name, function) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+/// Allocates a `Value::Number(Integer)` for a callback to return.
+#[no_mangle]
+pub extern "C" fn value_new_integer(value: i64) -> *mut Value {
+    Box::into_raw(Box::new(Value::Number(Number::Integer(value))))
+}
+
+/// Allocates a `Value::Number(Float)` for a callback to return.
+#[no_mangle]
+pub extern "C" fn value_new_float(value: c_double) -> *mut Value {
+    Box::into_raw(Box::new(Value::Number(Number::Float(value))))
+}
+
+/// Allocates a `Value::String` for a callback to return. Returns null if `value`
+/// is not valid UTF-8.
+#[no_mangle]
+pub extern "C" fn value_new_string(value: *const c_char) -> *mut Value {
+    match unsafe { CStr::from_ptr(value) }.to_str() {
+        Ok(s) => Box::into_raw(Box::new(Value::String(s.to_string()))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Allocates a `Value::Boolean` for a callback to return.
+#[no_mangle]
+pub extern "C" fn value_new_boolean(value: bool) -> *mut Value {
+    Box::into_raw(Box::new(Value::Boolean(value)))
+}
+
+/// Allocates a `Value::Nil` for a callback to return.
+#[no_mangle]
+pub extern "C" fn value_new_nil() -> *mut Value {
+    Box::into_raw(Box::new(Value::Nil))
+}
+
+/// Frees a `Value` allocated by one of the `value_new_*` helpers. This is the
+/// matching free hook for values a callback builds but decides not to return;
+/// values that *are* returned are reclaimed by the runtime and must not be freed.
+#[no_mangle]
+pub extern "C" fn value_free(value: *mut Value) {
+    if !value.is_null() {
+        unsafe {
+            drop(Box::from_raw(value));
+        }
+    }
+}
+
+/// Serializes the runtime's loaded state back to VTC text so embedders can
+/// persist it. Returns a heap `*mut c_char` the caller must release with
+/// [`runtime_free_string`], or null if the runtime pointer is invalid or the
+/// text contains an interior NUL.
+#[no_mangle]
+pub extern "C" fn runtime_dump_vtc(runtime: CRuntime) -> *mut c_char {
+    let runtime = match unsafe { runtime.0.as_mut() } {
+        Some(r) => r,
+        None => return ptr::null_mut(),
+    };
+    match CString::new(runtime.to_vtc_string()) {
+        Ok(s) => s.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn runtime_get_string(
     runtime: CRuntime,
@@ -4,9 +4,13 @@ use std::sync::Arc;
 
 use crate::value::Value;
 
+pub mod bignum;
 pub mod cffi;
+pub mod optimize;
 pub mod parser;
 pub mod runtime;
+pub mod schema;
+pub mod serializer;
 pub mod value;
 
 pub const SMALL_VEC_SIZE: usize = 512;
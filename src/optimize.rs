@@ -0,0 +1,184 @@
+//! A constant-folding pass over parsed [`Value`] trees.
+//!
+//! [`fold`] walks a [`Value`] (typically a [`Variable::value`](crate::value::Variable),
+//! or every variable of a freshly parsed [`VtcFile`](crate::value::VtcFile)) and:
+//!
+//! - simplifies algebraic identities regardless of whether the operand is itself
+//!   constant: `x + 0`, `0 + x`, `x - x`, `x * 1`, `1 * x` and `x * 0`, `0 * x`;
+//! - collapses any fully-constant [`Expr`] of [`Number`]s into a single `Number`,
+//!   following the runtime's arithmetic: `Integer`/`Binary`/`Hexadecimal`
+//!   operands fold to a plain `Integer` (`BigInt` on `i64` overflow), and the
+//!   result is promoted to `Float` when either operand is a float.
+//!
+//! [`Value::Reference`] nodes are left untouched — they depend on runtime
+//! resolution. Constant folding computes what a
+//! [`Runtime`](crate::runtime::Runtime) would; integer division by zero and
+//! integer operands outside the `i64` range are left unfolded for the runtime.
+//! The identity rules do not look at what `x` resolves to: `x - x` and `x * 0`
+//! fold to the integer `0` even where `x` is a float, and `x + 0` / `x * 1` fold
+//! to `x` even where `x` is not a number.
+
+use std::sync::Arc;
+
+use crate::bignum::BigInt;
+use crate::value::{BinaryOp, Expr, Number, UnaryOp, Value};
+
+/// Folds constant arithmetic and algebraic identities out of `value`, returning
+/// the simplified tree. Idempotent: folding the result again is a no-op.
+pub fn fold(value: Value) -> Value {
+	match value {
+		Value::List(list) => {
+			let folded = list.iter().cloned().map(fold).collect();
+			Value::List(Arc::new(folded))
+		}
+		Value::Map(map) => {
+			let folded = map.iter().map(|(k, v)| (Arc::clone(k), fold(v.clone()))).collect();
+			Value::Map(Arc::new(folded))
+		}
+		Value::Expr(expr) => fold_expr(*expr),
+		other => other,
+	}
+}
+
+fn fold_expr(expr: Expr) -> Value {
+	match expr {
+		Expr::Unary { op, operand } => {
+			let operand = fold(operand);
+			match (op, &operand) {
+				(UnaryOp::Neg, Value::Number(n)) => Value::Number(neg_number(n)),
+				_ => Value::Expr(Box::new(Expr::Unary { op, operand })),
+			}
+		}
+		Expr::Binary { op, lhs, rhs } => {
+			let lhs = fold(lhs);
+			let rhs = fold(rhs);
+
+			if let Some(simplified) = simplify_identity(op, &lhs, &rhs) {
+				return simplified;
+			}
+
+			// Integer division by zero and integer operands outside the `i64`
+			// range are left for the runtime to evaluate.
+			let foldable = match (&lhs, &rhs) {
+				(Value::Number(Number::Float(_)), Value::Number(_))
+				| (Value::Number(_), Value::Number(Number::Float(_))) => true,
+				(Value::Number(l), Value::Number(r)) => {
+					l.as_i64().is_some()
+						&& r.as_i64().is_some()
+						&& !(op == BinaryOp::Div && r.as_i64() == Some(0))
+				}
+				_ => false,
+			};
+
+			match (&lhs, &rhs) {
+				(Value::Number(l), Value::Number(r)) if is_foldable_arithmetic(op) && foldable => {
+					Value::Number(fold_numbers(op, l, r))
+				}
+				_ => Value::Expr(Box::new(Expr::Binary { op, lhs, rhs })),
+			}
+		}
+	}
+}
+
+/// Algebraic identities that hold no matter what `lhs`/`rhs` are, so they apply
+/// even when one side is a reference or another unresolved expression.
+fn simplify_identity(op: BinaryOp, lhs: &Value, rhs: &Value) -> Option<Value> {
+	match op {
+		BinaryOp::Add if is_zero(rhs) => Some(lhs.clone()),
+		BinaryOp::Add if is_zero(lhs) => Some(rhs.clone()),
+		BinaryOp::Sub if lhs == rhs => Some(Value::Number(Number::Integer(0))),
+		BinaryOp::Mul if is_one(rhs) => Some(lhs.clone()),
+		BinaryOp::Mul if is_one(lhs) => Some(rhs.clone()),
+		BinaryOp::Mul if is_zero(lhs) || is_zero(rhs) => Some(Value::Number(Number::Integer(0))),
+		_ => None,
+	}
+}
+
+fn is_zero(value: &Value) -> bool {
+	matches!(value.as_number(), Some(n) if n.as_i64() == Some(0) || matches!(n, Number::Float(f) if *f == 0.0))
+}
+
+fn is_one(value: &Value) -> bool {
+	matches!(value.as_number(), Some(n) if n.as_i64() == Some(1) || matches!(n, Number::Float(f) if *f == 1.0))
+}
+
+fn neg_number(n: Number) -> Number {
+	match n {
+		Number::Float(f) => Number::Float(-f),
+		Number::BigInt(b) => Number::from_bigint(b.neg()),
+		other => {
+			let i = other.as_i64().unwrap_or(0);
+			match i.checked_neg() {
+				Some(v) => Number::Integer(v),
+				None => Number::from_bigint(BigInt::from_i64(i).neg()),
+			}
+		}
+	}
+}
+
+/// `Add`/`Sub`/`Mul`/`Div` are the only ops that fold to a plain `Number`;
+/// `Lt`/`Gt`/`Eq` produce a `Boolean` and `Concat` operates on strings, so both
+/// are left for the runtime to evaluate.
+fn is_foldable_arithmetic(op: BinaryOp) -> bool {
+	matches!(op, BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div)
+}
+
+/// Folds an arithmetic op over two constant [`Number`]s, mirroring
+/// `Runtime::apply_binary_op`'s promotion rules: float if either side is a
+/// float, `BigInt` on `i64` overflow, `Integer`/`Binary`/`Hexadecimal` otherwise
+/// collapse to a plain `Integer` (the tag only matters for display of the
+/// original literal, not for the value it folds to).
+fn fold_numbers(op: BinaryOp, l: &Number, r: &Number) -> Number {
+	let float_mode = matches!(l, Number::Float(_)) || matches!(r, Number::Float(_));
+
+	match op {
+		_ if float_mode => {
+			let (l, r) = (number_as_f64(l), number_as_f64(r));
+			Number::Float(match op {
+				BinaryOp::Add => l + r,
+				BinaryOp::Sub => l - r,
+				BinaryOp::Mul => l * r,
+				BinaryOp::Div => l / r,
+				_ => unreachable!("Concat is handled before fold_numbers is reached"),
+			})
+		}
+		BinaryOp::Div => {
+			let (l, r) = (l.as_i64().unwrap_or(0), r.as_i64().unwrap_or(0));
+			if r == 0 {
+				// Not reached from `fold_expr`, which leaves integer division
+				// by zero unfolded for the runtime to report.
+				return Number::Integer(l);
+			}
+			Number::Integer(l / r)
+		}
+		_ => {
+			let (li, ri) = (l.as_i64().unwrap_or(0), r.as_i64().unwrap_or(0));
+			let checked = match op {
+				BinaryOp::Add => li.checked_add(ri),
+				BinaryOp::Sub => li.checked_sub(ri),
+				BinaryOp::Mul => li.checked_mul(ri),
+				_ => unreachable!("Concat is handled before fold_numbers is reached"),
+			};
+			match checked {
+				Some(v) => Number::Integer(v),
+				None => {
+					let (bl, br) = (BigInt::from_i64(li), BigInt::from_i64(ri));
+					Number::from_bigint(match op {
+						BinaryOp::Add => bl.add(&br),
+						BinaryOp::Sub => bl.sub(&br),
+						BinaryOp::Mul => bl.mul(&br),
+						_ => unreachable!("Concat is handled before fold_numbers is reached"),
+					})
+				}
+			}
+		}
+	}
+}
+
+fn number_as_f64(n: &Number) -> f64 {
+	match n {
+		Number::Float(f) => *f,
+		Number::BigInt(b) => b.to_f64(),
+		other => other.as_i64().unwrap_or(0) as f64,
+	}
+}
@@ -1,9 +1,18 @@
 use std::fmt;
 
 use crate::value::{
-	Accessor, Namespace, Number, Reference, ReferenceType, Value, Variable, VtcFile,
+	Accessor, BinaryOp, Expr, Namespace, Number, Reference, ReferenceType, UnaryOp, Value,
+	Variable, VtcFile,
 };
 
+/// Emits the canonical VTC text for a parsed file such that
+/// `parse(to_vtc_string(x))` is a fixed point: namespaces, `$var := value`
+/// bindings, lists, references (with their `&`/`%` prefix and `->` accessor
+/// chains) and the numeric literal forms all round-trip exactly.
+pub fn to_vtc_string(file: &VtcFile) -> String {
+	file.to_string()
+}
+
 impl fmt::Display for VtcFile {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		for (i, namespace) in self.namespaces.iter().enumerate() {
@@ -38,7 +47,7 @@ impl fmt::Display for Variable {
 impl fmt::Display for Value {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
-			Value::Intrinsic(i) => write!(f, "\"{}\"", i),
+			Value::Intrinsic(i) => write!(f, "{}!!", i),
 			Value::String(s) => write!(f, "\"{}\"", s),
 			Value::Number(n) => write!(f, "{}", n),
 			Value::Boolean(b) => write!(f, "{}", b),
@@ -53,11 +62,72 @@ impl fmt::Display for Value {
 				}
 				write!(f, "]")
 			}
+			Value::Map(m) => {
+				write!(f, "{{")?;
+				for (i, (key, val)) in m.iter().enumerate() {
+					if i > 0 {
+						write!(f, ", ")?;
+					}
+					write!(f, "{}: {}", key, val)?;
+				}
+				write!(f, "}}")
+			}
 			Value::Reference(r) => write!(f, "{}", r),
+			Value::Expr(e) => write!(f, "{}", e),
+		}
+	}
+}
+
+impl fmt::Display for Expr {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Expr::Unary { op, operand } => {
+				write!(f, "{}", op)?;
+				write_operand(f, operand)
+			}
+			Expr::Binary { op, lhs, rhs } => {
+				write_operand(f, lhs)?;
+				write!(f, " {} ", op)?;
+				write_operand(f, rhs)
+			}
+		}
+	}
+}
+
+/// Writes an expression operand, parenthesizing a nested expression so the
+/// printed text re-parses to the same tree regardless of precedence.
+fn write_operand(f: &mut fmt::Formatter<'_>, operand: &Value) -> fmt::Result {
+	match operand {
+		Value::Expr(_) => write!(f, "({})", operand),
+		_ => write!(f, "{}", operand),
+	}
+}
+
+impl fmt::Display for UnaryOp {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			UnaryOp::Neg => write!(f, "-"),
+			UnaryOp::Not => write!(f, "!"),
 		}
 	}
 }
 
+impl fmt::Display for BinaryOp {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let s = match self {
+			BinaryOp::Add => "+",
+			BinaryOp::Sub => "-",
+			BinaryOp::Mul => "*",
+			BinaryOp::Div => "/",
+			BinaryOp::Concat => "++",
+			BinaryOp::Lt => "<",
+			BinaryOp::Gt => ">",
+			BinaryOp::Eq => "==",
+		};
+		write!(f, "{}", s)
+	}
+}
+
 impl fmt::Display for Number {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
@@ -65,6 +135,7 @@ impl fmt::Display for Number {
 			Number::Float(fl) => write!(f, "{}", fl),
 			Number::Binary(b) => write!(f, "0b{:b}", b),
 			Number::Hexadecimal(h) => write!(f, "0x{:X}", h),
+			Number::BigInt(b) => write!(f, "{}", b),
 		}
 	}
 }
@@ -92,6 +163,11 @@ impl fmt::Display for Accessor {
 			Accessor::Index(i) => write!(f, "({})", i),
 			Accessor::Range(start, end) => write!(f, "({}..{})", start, end),
 			Accessor::Key(k) => write!(f, "[{}]", k),
+			Accessor::IndexFromEnd(n) => write!(f, "(-{})", n),
+			Accessor::RangeFrom(start) => write!(f, "({}..)", start),
+			Accessor::RangeTo(end) => write!(f, "(..{})", end),
+			Accessor::RangeFull => write!(f, "(..)"),
+			Accessor::Optional(inner) => write!(f, "?{}", inner),
 		}
 	}
 }
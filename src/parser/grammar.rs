@@ -3,13 +3,49 @@ use std::sync::Arc;
 use smallvec::SmallVec;
 
 use crate::parser::lexer::Token;
-use crate::value::{Accessor, Namespace, Number, Reference, ReferenceType, Value, Variable, VtcFile};
+use crate::value::{Accessor, BinaryOp, Expr, Namespace, Number, Reference, ReferenceType, UnaryOp, Value, Variable, VtcFile};
 
 const SMALL_VEC_SIZE: usize = 4;
 
+/// Binding power bound to unary prefix operators; higher than every binary
+/// operator so `-a * b` groups as `(-a) * b`.
+const PREFIX_BP: u8 = 7;
+
+/// A positioned parse error suitable for editor integration.
+///
+/// `position` is the byte offset of the offending token in the original source;
+/// `line`/`col` are the 1-based coordinates derived from it. `expected` lists
+/// the token descriptions the parser was looking for and `found` is the token it
+/// actually encountered (or `None` at end of input).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+	pub position: usize,
+	pub line: usize,
+	pub col: usize,
+	pub expected: Vec<String>,
+	pub found: Option<Token>,
+}
+
+impl std::fmt::Display for ParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}:{}: expected ", self.line, self.col)?;
+		if self.expected.is_empty() {
+			write!(f, "a different token")?;
+		} else {
+			write!(f, "{}", self.expected.join(" or "))?;
+		}
+		match &self.found {
+			Some(t) => write!(f, ", found {:?}", t),
+			None => write!(f, ", found end of input"),
+		}
+	}
+}
+
 pub struct Parser<'a> {
 	tokens: &'a [Token],
 	position: usize,
+	offsets: &'a [usize],
+	source: &'a str,
 }
 
 impl<'a> Parser<'a> {
@@ -17,6 +53,74 @@ impl<'a> Parser<'a> {
 		Parser {
 			tokens,
 			position: 0,
+			offsets: &[],
+			source: "",
+		}
+	}
+
+	/// Builds a parser that carries the source text and per-token byte offsets so
+	/// recovery can produce positioned [`ParseError`]s.
+	pub fn with_context(tokens: &'a [Token], offsets: &'a [usize], source: &'a str) -> Self {
+		Parser {
+			tokens,
+			position: 0,
+			offsets,
+			source,
+		}
+	}
+
+	/// Parses the whole token stream in recovering mode: on an error it records a
+	/// [`ParseError`], skips forward to the next namespace/variable synchronization
+	/// point, and keeps going, so a single run reports every problem.
+	pub fn parse_all(&mut self) -> Result<VtcFile, Vec<ParseError>> {
+		let mut namespaces = Vec::new();
+		let mut errors = Vec::new();
+
+		while self.position < self.tokens.len() {
+			match self.parse_namespace() {
+				Ok(ns) => namespaces.push(ns),
+				Err(msg) => {
+					errors.push(self.error_here(&msg));
+					self.synchronize();
+				}
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(VtcFile { namespaces })
+		} else {
+			Err(errors)
+		}
+	}
+
+	/// Builds a [`ParseError`] anchored at the current token, mapping its byte
+	/// offset to a 1-based line/column by scanning newlines in the source.
+	fn error_here(&self, expected: &str) -> ParseError {
+		let position = self
+			.offsets
+			.get(self.position.min(self.offsets.len().saturating_sub(1)))
+			.copied()
+			.unwrap_or_else(|| self.source.len());
+		let (line, col) = line_col(self.source, position);
+		ParseError {
+			position,
+			line,
+			col,
+			expected: vec![expected.to_string()],
+			found: self.peek_token().cloned(),
+		}
+	}
+
+	/// Skips tokens until the next namespace/variable boundary so parsing can
+	/// resume after a malformed definition.
+	fn synchronize(&mut self) {
+		// Always make progress past the offending token.
+		self.position += 1;
+		while self.position < self.tokens.len() {
+			match &self.tokens[self.position] {
+				Token::Namespace(_) | Token::Variable(_) => break,
+				_ => self.position += 1,
+			}
 		}
 	}
 
@@ -58,12 +162,58 @@ impl<'a> Parser<'a> {
 	}
 
 	fn parse_value(&mut self) -> Result<Value, String> {
+		self.parse_expression(0)
+	}
+
+	/// Precedence-climbing expression parser. Consumes a prefix, then while the
+	/// next token is a binary operator whose left binding power is at least
+	/// `min_bp`, consumes it and recurses with the operator's right binding power.
+	fn parse_expression(&mut self, min_bp: u8) -> Result<Value, String> {
+		let mut lhs = self.parse_prefix()?;
+
+		while let Some(op) = self.peek_binary_op() {
+			let (l_bp, r_bp) = op.binding_power();
+			if l_bp < min_bp {
+				break;
+			}
+			self.next_token(); // consume the operator
+			let rhs = self.parse_expression(r_bp)?;
+			lhs = Value::Expr(Box::new(Expr::Binary { op, lhs, rhs }));
+		}
+
+		Ok(lhs)
+	}
+
+	fn parse_prefix(&mut self) -> Result<Value, String> {
+		match self.peek_token() {
+			Some(Token::Minus) => {
+				self.next_token();
+				let operand = self.parse_expression(PREFIX_BP)?;
+				Ok(Value::Expr(Box::new(Expr::Unary { op: UnaryOp::Neg, operand })))
+			}
+			Some(Token::Bang) => {
+				self.next_token();
+				let operand = self.parse_expression(PREFIX_BP)?;
+				Ok(Value::Expr(Box::new(Expr::Unary { op: UnaryOp::Not, operand })))
+			}
+			Some(Token::OpenParen) => {
+				self.next_token();
+				let inner = self.parse_expression(0)?;
+				self.expect_token(|t| *t == Token::CloseParen)?;
+				Ok(inner)
+			}
+			_ => self.parse_atom(),
+		}
+	}
+
+	fn parse_atom(&mut self) -> Result<Value, String> {
 		match self.next_token() {
 			Some(token) => match token {
 				Token::OpenBracket => self.parse_list(),
 				Token::Intrinsic(i) => Ok(Value::Intrinsic(i.to_string())),
 				Token::String(s) => Ok(Value::String(s.to_string())),
 				Token::Integer(i) => Ok(Value::Number(Number::Integer(*i))),
+				Token::BigInteger(b) => Ok(Value::Number(Number::BigInt(b.clone()))),
 				Token::Float(f) => Ok(Value::Number(Number::Float(*f))),
 				Token::Binary(b) => Ok(Value::Number(Number::Binary(*b))),
 				Token::Hexadecimal(h) => Ok(Value::Number(Number::Hexadecimal(*h))),
@@ -76,6 +226,20 @@ impl<'a> Parser<'a> {
 		}
 	}
 
+	fn peek_binary_op(&self) -> Option<BinaryOp> {
+		match self.peek_token()? {
+			Token::Plus => Some(BinaryOp::Add),
+			Token::Minus => Some(BinaryOp::Sub),
+			Token::Star => Some(BinaryOp::Mul),
+			Token::Slash => Some(BinaryOp::Div),
+			Token::Concat => Some(BinaryOp::Concat),
+			Token::Lt => Some(BinaryOp::Lt),
+			Token::Gt => Some(BinaryOp::Gt),
+			Token::EqEq => Some(BinaryOp::Eq),
+			_ => None,
+		}
+	}
+
 	fn parse_list(&mut self) -> Result<Value, String> {
 		let mut values = Vec::new();
 		loop {
@@ -134,6 +298,11 @@ impl<'a> Parser<'a> {
 	fn parse_accessor(&mut self) -> Result<Accessor, String> {
 		match self.next_token() {
 			Some(token) => match token {
+				// `->?` marks the following navigation step as optional.
+				Token::Question => {
+					let inner = self.parse_accessor()?;
+					Ok(Accessor::Optional(Box::new(inner)))
+				}
 				Token::OpenParen => self.parse_index_or_range(),
 				Token::OpenBracket => self.parse_key(),
 				_ => Err(format!("Expected accessor, found {:?}", token)),
@@ -143,18 +312,48 @@ impl<'a> Parser<'a> {
 	}
 
 	fn parse_index_or_range(&mut self) -> Result<Accessor, String> {
-		let start = self.expect_token(|t| matches!(t, Token::Integer(_)))?;
+		// Open-ended ranges: `(..)` and `(..end)`.
+		if self.peek_token() == Some(&Token::Range) {
+			self.next_token();
+			if self.peek_token() == Some(&Token::CloseParen) {
+				self.next_token();
+				return Ok(Accessor::RangeFull);
+			}
+			let end = self.next_integer()?;
+			self.expect_token(|t| *t == Token::CloseParen)?;
+			return Ok(Accessor::RangeTo(non_negative(end, "range end")?));
+		}
+
+		let start = self.next_integer()?;
 		if self.peek_token() == Some(&Token::Range) {
 			self.next_token();
-			let end = self.expect_token(|t| matches!(t, Token::Integer(_)))?;
+			// `(start..)` leaves the end open.
+			if self.peek_token() == Some(&Token::CloseParen) {
+				self.next_token();
+				return Ok(Accessor::RangeFrom(non_negative(start, "range start")?));
+			}
+			let end = self.next_integer()?;
 			self.expect_token(|t| *t == Token::CloseParen)?;
 			Ok(Accessor::Range(
-				start.parse::<usize>().unwrap(),
-				end.parse::<usize>().unwrap(),
+				non_negative(start, "range start")?,
+				non_negative(end, "range end")?,
 			))
 		} else {
 			self.expect_token(|t| *t == Token::CloseParen)?;
-			Ok(Accessor::Index(start.parse::<usize>().unwrap()))
+			// A negative single index counts from the end.
+			if start < 0 {
+				Ok(Accessor::IndexFromEnd((-start) as usize))
+			} else {
+				Ok(Accessor::Index(start as usize))
+			}
+		}
+	}
+
+	/// Consumes an `Integer` token and returns its value, preserving sign.
+	fn next_integer(&mut self) -> Result<i64, String> {
+		match self.next_token() {
+			Some(Token::Integer(i)) => Ok(*i),
+			other => Err(format!("Expected integer, found {:?}", other)),
 		}
 	}
 
@@ -213,3 +412,38 @@ pub fn parse(tokens: &[Token]) -> Result<VtcFile, String> {
 	let mut parser = Parser::new(tokens);
 	parser.parse()
 }
+
+/// Recovering entry point: parses every namespace it can, collecting a
+/// positioned [`ParseError`] for each failure instead of bailing on the first.
+pub fn parse_all(tokens: &[Token], offsets: &[usize], source: &str) -> Result<VtcFile, Vec<ParseError>> {
+	let mut parser = Parser::with_context(tokens, offsets, source);
+	parser.parse_all()
+}
+
+/// Rejects a negative value in a position where only a non-negative index is
+/// meaningful (range bounds).
+fn non_negative(value: i64, what: &str) -> Result<usize, String> {
+	if value < 0 {
+		Err(format!("{} cannot be negative: {}", what, value))
+	} else {
+		Ok(value as usize)
+	}
+}
+
+/// Maps a byte offset to a 1-based `(line, column)` by scanning newlines.
+fn line_col(source: &str, offset: usize) -> (usize, usize) {
+	let mut line = 1;
+	let mut col = 1;
+	for (i, ch) in source.char_indices() {
+		if i >= offset {
+			break;
+		}
+		if ch == '\n' {
+			line += 1;
+			col = 1;
+		} else {
+			col += 1;
+		}
+	}
+	(line, col)
+}
@@ -11,6 +11,8 @@ use nom::{
 use smallvec::SmallVec;
 use std::sync::Arc;
 
+use crate::bignum::BigInt;
+
 pub const INLINE_CAPACITY: usize = 16;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -25,6 +27,8 @@ pub enum Token {
 	Comma,
 	String(Arc<String>),
 	Integer(i64),
+	/// An integer literal that does not fit in an `i64`.
+	BigInteger(BigInt),
 	Float(f64),
 	Binary(i64),
 	Hexadecimal(i64),
@@ -36,12 +40,38 @@ pub enum Token {
 	Range,
 	Identifier(Arc<String>),
 	Colon,
+	Question,
 	Intrinsic(Arc<String>),
 	Comment(Arc<String>),
+	Plus,
+	Minus,
+	Star,
+	Slash,
+	Concat,
+	Bang,
+	Lt,
+	Gt,
+	EqEq,
 }
 
 type TokenVec = SmallVec<[Token; INLINE_CAPACITY]>;
 
+/// A half-open byte range `[start, end)` into the original source text.
+///
+/// Spans are monotonic and collectively cover the whole input, so the first
+/// unconsumed token's span can be used to point at where tokenization stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+	pub start: usize,
+	pub end: usize,
+}
+
+impl Span {
+	pub fn new(start: usize, end: usize) -> Self {
+		Span { start, end }
+	}
+}
+
 #[derive(Debug)]
 pub enum VtcError<I> {
 	Nom(I, ErrorKind),
@@ -87,6 +117,39 @@ pub fn tokenize(input: &str) -> IResult<&str, TokenVec, VtcError<&str>> {
 	))(input)
 }
 
+/// Tokenizes `input` while recording the starting byte offset of every token in
+/// the original source, alongside the byte offset where tokenization stopped
+/// (the whole input's length on full success). The offsets are derived from
+/// how much input remains after each token is consumed, so they stay
+/// monotonic and cover the whole input. Used by the recovering grammar path to
+/// build positioned diagnostics, and by [`crate::parser::parse_vtc`] to point
+/// at the first byte a failed fast-path lex couldn't consume.
+pub fn tokenize_with_offsets(input: &str) -> Result<(Vec<(Token, usize)>, usize), VtcError<&str>> {
+	let mut tokens = Vec::new();
+	let mut remaining = input;
+	loop {
+		// Skip leading whitespace before recording the token position.
+		let (after_ws, _) = multispace0::<_, VtcError<&str>>(remaining)
+			.map_err(|_| VtcError::Parse("whitespace".to_string()))?;
+		if after_ws.is_empty() {
+			remaining = after_ws;
+			break;
+		}
+		let start = input.len() - after_ws.len();
+		match alt((parse_simple_tokens, parse_complex_tokens))(after_ws) {
+			Ok((rest, token)) => {
+				tokens.push((token, start));
+				remaining = rest;
+			}
+			Err(_) => {
+				remaining = after_ws;
+				break;
+			}
+		}
+	}
+	Ok((tokens, input.len() - remaining.len()))
+}
+
 fn parse_simple_tokens(input: &str) -> IResult<&str, Token, VtcError<&str>> {
 	alt((
 		value(Token::Assign, tag(":=")),
@@ -99,6 +162,14 @@ fn parse_simple_tokens(input: &str) -> IResult<&str, Token, VtcError<&str>> {
 		value(Token::Range, tag("..")),
 		value(Token::Dot, char('.')),
 		value(Token::Colon, char(':')),
+		value(Token::Question, char('?')),
+		value(Token::EqEq, tag("==")),
+		value(Token::Concat, tag("++")),
+		value(Token::Plus, char('+')),
+		value(Token::Star, char('*')),
+		value(Token::Slash, char('/')),
+		value(Token::Lt, char('<')),
+		value(Token::Gt, char('>')),
 		value(Token::Nil, tag("Nil")),
 	))(input)
 }
@@ -111,12 +182,18 @@ fn parse_complex_tokens(input: &str) -> IResult<&str, Token, VtcError<&str>> {
 		map(parse_intrinsic, |s| Token::Intrinsic(Arc::new(s))),
 		map(parse_string, |s| Token::String(Arc::new(s))),
 		map(parse_binary, Token::Binary),
+		map(parse_big_hexadecimal, Token::BigInteger),
 		map(parse_hexadecimal, Token::Hexadecimal),
 		map(parse_float, Token::Float),
+		map(parse_big_integer, Token::BigInteger),
 		map(parse_integer, Token::Integer),
 		map(parse_boolean, Token::Boolean),
 		map(parse_reference, |s| Token::Reference(Arc::new(s))),
 		map(parse_identifier, |s| Token::Identifier(Arc::new(s))),
+		// Subtraction binds looser than a negative literal, so numbers above
+		// claim `-5` first and a bare `-` falls through to here.
+		value(Token::Minus, char('-')),
+		value(Token::Bang, char('!')),
 	))(input)
 }
 
@@ -172,6 +249,32 @@ pub fn parse_integer(input: &str) -> IResult<&str, i64, VtcError<&str>> {
 	})(input)
 }
 
+/// Parses a decimal integer literal that overflows `i64`, falling back to the
+/// arbitrary-precision representation. Fails (so `parse_integer` wins) when the
+/// literal fits in an `i64`.
+pub fn parse_big_integer(input: &str) -> IResult<&str, BigInt, VtcError<&str>> {
+	let (rest, s) = recognize(pair(opt(char('-')), digit1))(input)?;
+	if s.parse::<i64>().is_ok() {
+		return Err(nom::Err::Error(VtcError::from_error_kind(input, ErrorKind::Digit)));
+	}
+	match BigInt::from_decimal(s) {
+		Some(b) => Ok((rest, b)),
+		None => Err(nom::Err::Error(VtcError::from_error_kind(input, ErrorKind::Digit))),
+	}
+}
+
+/// Parses a hexadecimal literal that overflows `i64`, producing a `BigInt`.
+pub fn parse_big_hexadecimal(input: &str) -> IResult<&str, BigInt, VtcError<&str>> {
+	let (rest, s) = preceded(tag("0x"), take_while1(|c: char| c.is_digit(16)))(input)?;
+	if i64::from_str_radix(s, 16).is_ok() {
+		return Err(nom::Err::Error(VtcError::from_error_kind(input, ErrorKind::HexDigit)));
+	}
+	match BigInt::from_hex(s) {
+		Some(b) => Ok((rest, b)),
+		None => Err(nom::Err::Error(VtcError::from_error_kind(input, ErrorKind::HexDigit))),
+	}
+}
+
 pub fn parse_float(input: &str) -> IResult<&str, f64, VtcError<&str>> {
 	map(
 		recognize(tuple((opt(char('-')), digit1, char('.'), digit1))),
@@ -1,4 +1,5 @@
-use crate::parser::lexer::tokenize;
+use crate::parser::grammar::ParseError;
+use crate::parser::lexer::{tokenize, tokenize_with_offsets, Span};
 use crate::runtime::error::RuntimeError;
 use crate::value::VtcFile;
 
@@ -10,9 +11,89 @@ pub fn parse_vtc(input: &str) -> Result<VtcFile, RuntimeError> {
 	let (remaining, tokens) = tokenize(input)
 		.map_err(|e| RuntimeError::ParseError(format!("Tokenization failed: {:?}", e)))?;
 	if !remaining.is_empty() {
-		return Err(RuntimeError::ParseError(
-			"Input was not fully parsed".to_string(),
-		));
+		// Point the diagnostic at the first byte the lexer could not consume.
+		let (_, unconsumed) = tokenize_with_offsets(input).unwrap_or_else(|_| (Vec::new(), 0));
+		return Err(RuntimeError::ParseError(render_diagnostic(
+			input,
+			unconsumed,
+			"Input was not fully parsed",
+		)));
 	}
-	grammar::parse(&tokens).map_err(|e| RuntimeError::ParseError(e.to_string()))
+	grammar::parse(&tokens).map_err(|msg| {
+		// Re-run the recovering parser to recover an exact location for the
+		// grammar failure, then render a caret-underlined snippet.
+		match parse_vtc_all(input) {
+			Err(errors) if !errors.is_empty() => {
+				let first = &errors[0];
+				RuntimeError::ParseError(render_diagnostic(input, first.position, &first.to_string()))
+			}
+			_ => RuntimeError::ParseError(msg),
+		}
+	})
+}
+
+/// Renders a one-line caret-underlined view of `source` at `offset`, prefixed
+/// with the `1:1`-style coordinate and the supplied message.
+pub fn render_diagnostic(source: &str, offset: usize, message: &str) -> String {
+	let offset = offset.min(source.len());
+
+	// Find the bounds of the line containing `offset`.
+	let line_start = source[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+	let line_end = source[offset..]
+		.find('\n')
+		.map(|i| offset + i)
+		.unwrap_or(source.len());
+
+	let line_no = source[..offset].bytes().filter(|&b| b == b'\n').count() + 1;
+	let col = offset - line_start + 1;
+	let line = &source[line_start..line_end];
+
+	let mut out = String::new();
+	out.push_str(&format!("{}:{}: {}\n", line_no, col, message));
+	out.push_str(line);
+	out.push('\n');
+	out.push_str(&" ".repeat(col.saturating_sub(1)));
+	out.push('^');
+	out
+}
+
+/// Convenience renderer for a [`Span`].
+pub fn render_span(source: &str, span: Span, message: &str) -> String {
+	render_diagnostic(source, span.start, message)
+}
+
+/// Parses `input` in recovering mode, returning the parsed file, or a
+/// positioned [`ParseError`] for every problem encountered.
+///
+/// Unlike [`parse_vtc`], this does not stop at the first error, which makes it
+/// suitable for editor integration and batch validation.
+pub fn parse_vtc_all(input: &str) -> Result<VtcFile, Vec<ParseError>> {
+	let (spanned, stop) = tokenize_with_offsets(input).map_err(|_| {
+		vec![ParseError {
+			position: 0,
+			line: 1,
+			col: 1,
+			expected: vec!["a valid token".to_string()],
+			found: None,
+		}]
+	})?;
+
+	let tokens: Vec<_> = spanned.iter().map(|(t, _)| t.clone()).collect();
+	let offsets: Vec<_> = spanned.iter().map(|(_, o)| *o).collect();
+	let result = grammar::parse_all(&tokens, &offsets, input);
+	if stop == input.len() {
+		return result;
+	}
+
+	// The lexer stopped early: report the first byte it could not consume.
+	let line_start = input[..stop].rfind('\n').map_or(0, |i| i + 1);
+	let mut errors = result.err().unwrap_or_default();
+	errors.push(ParseError {
+		position: stop,
+		line: input[..stop].matches('\n').count() + 1,
+		col: stop - line_start + 1,
+		expected: vec!["a valid token".to_string()],
+		found: None,
+	});
+	Err(errors)
 }
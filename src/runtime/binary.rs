@@ -0,0 +1,511 @@
+//! Canonical binary codec for [`Value`] and whole runtimes.
+//!
+//! The textual VTC dump ([`Runtime::dump_to_file`]) is lossy — a whole-number
+//! float such as `1.0` is written as `1` and reads back as an integer — and slow
+//! to reparse for large configs.
+//! This module adds a compact, self-describing byte stream alongside it.
+//!
+//! # Format
+//!
+//! The scheme is a tag-length-value encoding modeled on Preserves' binary
+//! transfer syntax. Every value begins with one [tag byte](tag) selecting the
+//! variant. Variable-width payloads (strings, lists, references, maps) carry an
+//! unsigned LEB128 length prefix followed by their contents. Signed integers are
+//! zig-zag + LEB128; floats are a fixed 8-byte IEEE-754 big-endian payload. The
+//! `Number` sub-variant tag is preserved, so `0b`/`0x` radix survives the round
+//! trip.
+//!
+//! # Canonicality
+//!
+//! Field order is fixed and namespaces are emitted sorted by name (mirroring
+//! [`Runtime::dump_selective`]), so decoding then re-encoding yields a
+//! byte-identical stream.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use fnv::FnvHashMap;
+use smallvec::SmallVec;
+
+use crate::bignum::BigInt;
+use crate::runtime::Runtime;
+use crate::value::{Accessor, BinaryOp, Expr, Number, Reference, ReferenceType, UnaryOp, Value};
+
+/// One-byte discriminators for each encoded form. The `Number` sub-variants get
+/// distinct tags so radix intent is preserved across a round trip.
+mod tag {
+	pub const STRING: u8 = 0x01;
+	pub const INTEGER: u8 = 0x02;
+	pub const FLOAT: u8 = 0x03;
+	pub const BINARY: u8 = 0x04;
+	pub const HEXADECIMAL: u8 = 0x05;
+	pub const BOOLEAN: u8 = 0x06;
+	pub const NIL: u8 = 0x07;
+	pub const LIST: u8 = 0x08;
+	pub const REFERENCE: u8 = 0x09;
+	pub const INTRINSIC: u8 = 0x0A;
+	pub const BIGINT: u8 = 0x0B;
+	pub const MAP: u8 = 0x0C;
+	pub const EXPR: u8 = 0x0D;
+}
+
+/// Accessor discriminators, written before each accessor in a reference's chain.
+mod acc_tag {
+	pub const INDEX: u8 = 0x01;
+	pub const RANGE: u8 = 0x02;
+	pub const KEY: u8 = 0x03;
+	pub const INDEX_FROM_END: u8 = 0x04;
+	pub const RANGE_FROM: u8 = 0x05;
+	pub const RANGE_TO: u8 = 0x06;
+	pub const RANGE_FULL: u8 = 0x07;
+	pub const OPTIONAL: u8 = 0x08;
+}
+
+impl Runtime {
+	/// Serializes the whole runtime to `path` in the canonical binary format.
+	///
+	/// Namespaces and the variables within each are written in sorted order, so
+	/// the output is byte-stable for a given state.
+	pub fn dump_binary_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		if let Some(parent) = path.as_ref().parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+
+		let file = File::create(path)?;
+		let mut writer = BufWriter::new(file);
+		let bytes = self.encode_runtime();
+		writer.write_all(&bytes)?;
+		writer.flush()?;
+		Ok(())
+	}
+
+	/// Reads a runtime previously written by [`Runtime::dump_binary_to_file`].
+	pub fn load_binary<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+		let mut file = File::open(path)?;
+		let mut bytes = Vec::new();
+		file.read_to_end(&mut bytes)?;
+
+		let mut runtime = Runtime::new();
+		let mut reader = Reader::new(&bytes);
+		let ns_count = reader.read_uleb()? as usize;
+		for _ in 0..ns_count {
+			let name = Arc::new(reader.read_string()?);
+			let var_count = reader.read_uleb()? as usize;
+			let mut variables =
+				FnvHashMap::with_capacity_and_hasher(var_count, Default::default());
+			for _ in 0..var_count {
+				let var_name = Arc::new(reader.read_string()?);
+				let value = reader.read_value()?;
+				variables.insert(var_name, Arc::new(value));
+			}
+			runtime.namespaces.insert(name, variables);
+		}
+		Ok(runtime)
+	}
+
+	/// Encodes the runtime's namespaces into a canonical byte vector.
+	fn encode_runtime(&self) -> Vec<u8> {
+		let mut out = Vec::new();
+
+		let mut namespaces: Vec<_> = self.namespaces.iter().collect();
+		namespaces.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+		write_uleb(&mut out, namespaces.len() as u64);
+		for (namespace, variables) in namespaces {
+			write_string(&mut out, namespace);
+
+			let mut vars: Vec<_> = variables.iter().collect();
+			vars.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+
+			write_uleb(&mut out, vars.len() as u64);
+			for (var_name, value) in vars {
+				write_string(&mut out, var_name);
+				encode_value(value, &mut out);
+			}
+		}
+
+		out
+	}
+}
+
+/// Appends the canonical encoding of `value` to `out`.
+pub fn encode_value(value: &Value, out: &mut Vec<u8>) {
+	match value {
+		Value::String(s) => {
+			out.push(tag::STRING);
+			write_string(out, s);
+		}
+		Value::Number(number) => encode_number(number, out),
+		Value::Boolean(b) => {
+			out.push(tag::BOOLEAN);
+			out.push(u8::from(*b));
+		}
+		Value::Nil => out.push(tag::NIL),
+		Value::List(items) => {
+			out.push(tag::LIST);
+			write_uleb(out, items.len() as u64);
+			for item in items.iter() {
+				encode_value(item, out);
+			}
+		}
+		Value::Map(entries) => {
+			out.push(tag::MAP);
+			write_uleb(out, entries.len() as u64);
+			for (key, val) in entries.iter() {
+				write_string(out, key);
+				encode_value(val, out);
+			}
+		}
+		Value::Reference(reference) => {
+			out.push(tag::REFERENCE);
+			encode_reference(reference, out);
+		}
+		Value::Intrinsic(name) => {
+			out.push(tag::INTRINSIC);
+			write_string(out, name);
+		}
+		Value::Expr(expr) => {
+			out.push(tag::EXPR);
+			encode_expr(expr, out);
+		}
+	}
+}
+
+/// Decodes a single value from `bytes`, returning it along with the number of
+/// bytes consumed.
+pub fn decode_value(bytes: &[u8]) -> io::Result<(Value, usize)> {
+	let mut reader = Reader::new(bytes);
+	let value = reader.read_value()?;
+	Ok((value, reader.pos))
+}
+
+fn encode_number(number: &Number, out: &mut Vec<u8>) {
+	match number {
+		Number::Integer(i) => {
+			out.push(tag::INTEGER);
+			write_zigzag(out, *i);
+		}
+		Number::Binary(i) => {
+			out.push(tag::BINARY);
+			write_zigzag(out, *i);
+		}
+		Number::Hexadecimal(i) => {
+			out.push(tag::HEXADECIMAL);
+			write_zigzag(out, *i);
+		}
+		Number::Float(f) => {
+			out.push(tag::FLOAT);
+			out.extend_from_slice(&f.to_be_bytes());
+		}
+		Number::BigInt(b) => {
+			out.push(tag::BIGINT);
+			// Canonical decimal text; the sign is part of the string.
+			write_string(out, &b.to_string());
+		}
+	}
+}
+
+fn encode_reference(reference: &Reference, out: &mut Vec<u8>) {
+	out.push(match reference.ref_type {
+		ReferenceType::External => 0,
+		ReferenceType::Local => 1,
+	});
+	// A zero-length namespace encodes `None`.
+	match &reference.namespace {
+		Some(ns) => write_string(out, ns),
+		None => write_uleb(out, 0),
+	}
+	write_string(out, &reference.variable);
+	write_uleb(out, reference.accessors.len() as u64);
+	for accessor in &reference.accessors {
+		encode_accessor(accessor, out);
+	}
+}
+
+fn encode_accessor(accessor: &Accessor, out: &mut Vec<u8>) {
+	match accessor {
+		Accessor::Index(i) => {
+			out.push(acc_tag::INDEX);
+			write_uleb(out, *i as u64);
+		}
+		Accessor::Range(start, end) => {
+			out.push(acc_tag::RANGE);
+			write_uleb(out, *start as u64);
+			write_uleb(out, *end as u64);
+		}
+		Accessor::Key(key) => {
+			out.push(acc_tag::KEY);
+			write_string(out, key);
+		}
+		Accessor::IndexFromEnd(n) => {
+			out.push(acc_tag::INDEX_FROM_END);
+			write_uleb(out, *n as u64);
+		}
+		Accessor::RangeFrom(start) => {
+			out.push(acc_tag::RANGE_FROM);
+			write_uleb(out, *start as u64);
+		}
+		Accessor::RangeTo(end) => {
+			out.push(acc_tag::RANGE_TO);
+			write_uleb(out, *end as u64);
+		}
+		Accessor::RangeFull => out.push(acc_tag::RANGE_FULL),
+		Accessor::Optional(inner) => {
+			out.push(acc_tag::OPTIONAL);
+			encode_accessor(inner, out);
+		}
+	}
+}
+
+fn encode_expr(expr: &Expr, out: &mut Vec<u8>) {
+	match expr {
+		Expr::Unary { op, operand } => {
+			out.push(0);
+			out.push(match op {
+				UnaryOp::Neg => 0,
+				UnaryOp::Not => 1,
+			});
+			encode_value(operand, out);
+		}
+		Expr::Binary { op, lhs, rhs } => {
+			out.push(1);
+			out.push(binary_op_code(*op));
+			encode_value(lhs, out);
+			encode_value(rhs, out);
+		}
+	}
+}
+
+fn binary_op_code(op: BinaryOp) -> u8 {
+	match op {
+		BinaryOp::Add => 0,
+		BinaryOp::Sub => 1,
+		BinaryOp::Mul => 2,
+		BinaryOp::Div => 3,
+		BinaryOp::Concat => 4,
+		BinaryOp::Lt => 5,
+		BinaryOp::Gt => 6,
+		BinaryOp::Eq => 7,
+	}
+}
+
+fn binary_op_from_code(code: u8) -> io::Result<BinaryOp> {
+	Ok(match code {
+		0 => BinaryOp::Add,
+		1 => BinaryOp::Sub,
+		2 => BinaryOp::Mul,
+		3 => BinaryOp::Div,
+		4 => BinaryOp::Concat,
+		5 => BinaryOp::Lt,
+		6 => BinaryOp::Gt,
+		7 => BinaryOp::Eq,
+		other => return Err(invalid(format!("unknown binary op code {other}"))),
+	})
+}
+
+/// A positional reader over an encoded byte slice.
+struct Reader<'a> {
+	bytes: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Reader<'a> {
+	fn new(bytes: &'a [u8]) -> Self {
+		Reader { bytes, pos: 0 }
+	}
+
+	fn read_byte(&mut self) -> io::Result<u8> {
+		let byte = *self
+			.bytes
+			.get(self.pos)
+			.ok_or_else(|| invalid("unexpected end of input"))?;
+		self.pos += 1;
+		Ok(byte)
+	}
+
+	fn read_uleb(&mut self) -> io::Result<u64> {
+		let mut result: u64 = 0;
+		let mut shift = 0;
+		loop {
+			let byte = self.read_byte()?;
+			result |= u64::from(byte & 0x7f) << shift;
+			if byte & 0x80 == 0 {
+				break;
+			}
+			shift += 7;
+			if shift >= 64 {
+				return Err(invalid("LEB128 value overflows u64"));
+			}
+		}
+		Ok(result)
+	}
+
+	fn read_zigzag(&mut self) -> io::Result<i64> {
+		let raw = self.read_uleb()?;
+		Ok(((raw >> 1) as i64) ^ -((raw & 1) as i64))
+	}
+
+	fn read_bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+		let end = self
+			.pos
+			.checked_add(len)
+			.filter(|end| *end <= self.bytes.len())
+			.ok_or_else(|| invalid("length prefix runs past end of input"))?;
+		let slice = &self.bytes[self.pos..end];
+		self.pos = end;
+		Ok(slice)
+	}
+
+	fn read_string(&mut self) -> io::Result<String> {
+		let len = self.read_uleb()? as usize;
+		let slice = self.read_bytes(len)?;
+		String::from_utf8(slice.to_vec()).map_err(|_| invalid("string is not valid UTF-8"))
+	}
+
+	fn read_value(&mut self) -> io::Result<Value> {
+		let tag = self.read_byte()?;
+		Ok(match tag {
+			tag::STRING => Value::String(self.read_string()?),
+			tag::INTEGER => Value::Number(Number::Integer(self.read_zigzag()?)),
+			tag::BINARY => Value::Number(Number::Binary(self.read_zigzag()?)),
+			tag::HEXADECIMAL => Value::Number(Number::Hexadecimal(self.read_zigzag()?)),
+			tag::FLOAT => {
+				let slice = self.read_bytes(8)?;
+				let mut buf = [0u8; 8];
+				buf.copy_from_slice(slice);
+				Value::Number(Number::Float(f64::from_be_bytes(buf)))
+			}
+			tag::BIGINT => {
+				let text = self.read_string()?;
+				let big = BigInt::from_decimal(&text)
+					.ok_or_else(|| invalid("invalid BigInt decimal payload"))?;
+				Value::Number(Number::BigInt(big))
+			}
+			tag::BOOLEAN => Value::Boolean(self.read_byte()? != 0),
+			tag::NIL => Value::Nil,
+			tag::LIST => {
+				let len = self.read_uleb()? as usize;
+				let mut items = Vec::with_capacity(len);
+				for _ in 0..len {
+					items.push(self.read_value()?);
+				}
+				Value::List(Arc::new(items))
+			}
+			tag::MAP => {
+				let len = self.read_uleb()? as usize;
+				let mut entries = Vec::with_capacity(len);
+				for _ in 0..len {
+					let key = Arc::new(self.read_string()?);
+					let val = self.read_value()?;
+					entries.push((key, val));
+				}
+				Value::Map(Arc::new(entries))
+			}
+			tag::REFERENCE => Value::Reference(self.read_reference()?),
+			tag::INTRINSIC => Value::Intrinsic(self.read_string()?),
+			tag::EXPR => Value::Expr(Box::new(self.read_expr()?)),
+			other => return Err(invalid(format!("unknown value tag {other}"))),
+		})
+	}
+
+	fn read_reference(&mut self) -> io::Result<Reference> {
+		let ref_type = match self.read_byte()? {
+			0 => ReferenceType::External,
+			1 => ReferenceType::Local,
+			other => return Err(invalid(format!("unknown reference type {other}"))),
+		};
+		let ns_text = self.read_string()?;
+		let namespace = if ns_text.is_empty() {
+			None
+		} else {
+			Some(Arc::new(ns_text))
+		};
+		let variable = Arc::new(self.read_string()?);
+		let acc_count = self.read_uleb()? as usize;
+		let mut accessors = SmallVec::new();
+		for _ in 0..acc_count {
+			accessors.push(self.read_accessor()?);
+		}
+		Ok(Reference {
+			ref_type,
+			namespace,
+			variable,
+			accessors,
+		})
+	}
+
+	fn read_accessor(&mut self) -> io::Result<Accessor> {
+		let tag = self.read_byte()?;
+		Ok(match tag {
+			acc_tag::INDEX => Accessor::Index(self.read_uleb()? as usize),
+			acc_tag::RANGE => {
+				let start = self.read_uleb()? as usize;
+				let end = self.read_uleb()? as usize;
+				Accessor::Range(start, end)
+			}
+			acc_tag::KEY => Accessor::Key(self.read_string()?),
+			acc_tag::INDEX_FROM_END => Accessor::IndexFromEnd(self.read_uleb()? as usize),
+			acc_tag::RANGE_FROM => Accessor::RangeFrom(self.read_uleb()? as usize),
+			acc_tag::RANGE_TO => Accessor::RangeTo(self.read_uleb()? as usize),
+			acc_tag::RANGE_FULL => Accessor::RangeFull,
+			acc_tag::OPTIONAL => Accessor::Optional(Box::new(self.read_accessor()?)),
+			other => return Err(invalid(format!("unknown accessor tag {other}"))),
+		})
+	}
+
+	fn read_expr(&mut self) -> io::Result<Expr> {
+		match self.read_byte()? {
+			0 => {
+				let op = match self.read_byte()? {
+					0 => UnaryOp::Neg,
+					1 => UnaryOp::Not,
+					other => return Err(invalid(format!("unknown unary op code {other}"))),
+				};
+				Ok(Expr::Unary {
+					op,
+					operand: self.read_value()?,
+				})
+			}
+			1 => {
+				let op = binary_op_from_code(self.read_byte()?)?;
+				let lhs = self.read_value()?;
+				let rhs = self.read_value()?;
+				Ok(Expr::Binary { op, lhs, rhs })
+			}
+			other => Err(invalid(format!("unknown expr kind {other}"))),
+		}
+	}
+}
+
+/// Writes an unsigned LEB128 integer.
+fn write_uleb(out: &mut Vec<u8>, mut value: u64) {
+	loop {
+		let mut byte = (value & 0x7f) as u8;
+		value >>= 7;
+		if value != 0 {
+			byte |= 0x80;
+		}
+		out.push(byte);
+		if value == 0 {
+			break;
+		}
+	}
+}
+
+/// Writes a signed integer as zig-zag + LEB128.
+fn write_zigzag(out: &mut Vec<u8>, value: i64) {
+	let zigzag = ((value << 1) ^ (value >> 63)) as u64;
+	write_uleb(out, zigzag);
+}
+
+/// Writes a length-prefixed UTF-8 string.
+fn write_string(out: &mut Vec<u8>, s: &str) {
+	write_uleb(out, s.len() as u64);
+	out.extend_from_slice(s.as_bytes());
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
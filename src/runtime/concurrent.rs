@@ -0,0 +1,194 @@
+//! A genuinely concurrent variant of [`Runtime`].
+//!
+//! [`Runtime`] documents itself as thread-safe, but every mutator takes
+//! `&mut self`, so a shared instance cannot serve reads while another thread
+//! writes. [`ConcurrentRuntime`] keeps the outer namespace map fixed behind a
+//! shared `&self` and wraps each namespace's variable map in its own
+//! [`RwLock`]. Many threads can therefore read — or mutate different namespaces
+//! — at the same time; a writer of a namespace waits for, and blocks, every
+//! other reader and writer of that *same* namespace.
+//!
+//! The scoped accessors [`ConcurrentRuntime::with_namespace`] and
+//! [`ConcurrentRuntime::maybe_with_namespace`] hand a closure a locked view of a
+//! single namespace, so callers acquire the lock once for a batch of work
+//! instead of re-locking per key.
+//!
+//! Deep reference resolution (cycle detection, accessors, intrinsics) stays in
+//! [`Runtime`]; call [`ConcurrentRuntime::snapshot`] to obtain a plain runtime
+//! over a copy of the current state when full resolution is needed.
+
+use std::sync::{Arc, RwLock};
+
+use fnv::FnvHashMap;
+
+use crate::runtime::error::RuntimeError;
+use crate::runtime::Runtime;
+use crate::value::Value;
+
+/// The variable map held inside a single namespace.
+type NamespaceMap = FnvHashMap<Arc<String>, Arc<Value>>;
+
+/// A thread-safe runtime usable behind an `Arc` from a multi-threaded config
+/// server. See the [module documentation](self) for the locking model.
+#[derive(Debug, Default)]
+pub struct ConcurrentRuntime {
+	namespaces: FnvHashMap<Arc<String>, RwLock<NamespaceMap>>,
+}
+
+impl ConcurrentRuntime {
+	/// Creates a new, empty concurrent runtime.
+	pub fn new() -> Self {
+		ConcurrentRuntime { namespaces: FnvHashMap::default() }
+	}
+
+	/// Builds a concurrent runtime from an existing [`Runtime`], taking ownership
+	/// of its namespaces.
+	pub fn from_runtime(runtime: Runtime) -> Self {
+		let namespaces = runtime
+			.namespaces
+			.into_iter()
+			.map(|(name, vars)| (name, RwLock::new(vars)))
+			.collect();
+		ConcurrentRuntime { namespaces }
+	}
+
+	/// Produces a plain [`Runtime`] over a copy of the current state, for deep
+	/// reference resolution. Each namespace is read-locked in turn, so every
+	/// namespace is copied consistently, but a write to another namespace can
+	/// land between two of those copies.
+	pub fn snapshot(&self) -> Runtime {
+		let mut runtime = Runtime::new();
+		runtime.namespaces = self
+			.namespaces
+			.iter()
+			.map(|(name, lock)| {
+				let vars = lock.read().expect("namespace lock poisoned").clone();
+				(Arc::clone(name), vars)
+			})
+			.collect();
+		runtime
+	}
+
+	/// Runs `f` against a read-locked view of `namespace`.
+	///
+	/// Returns [`RuntimeError::NamespaceNotFound`] when the namespace is absent.
+	/// Use [`maybe_with_namespace`](Self::maybe_with_namespace) when a missing
+	/// namespace is not an error.
+	pub fn with_namespace<F, R>(&self, namespace: &str, f: F) -> Result<R, RuntimeError>
+	where
+		F: FnOnce(&NamespaceMap) -> R,
+	{
+		match self.namespaces.get(&Arc::new(namespace.to_string())) {
+			Some(lock) => {
+				let guard = lock.read().map_err(|_| {
+					RuntimeError::CustomFunctionError(format!("namespace `{}` lock poisoned", namespace))
+				})?;
+				Ok(f(&guard))
+			}
+			None => Err(RuntimeError::NamespaceNotFound(namespace.to_string())),
+		}
+	}
+
+	/// Like [`with_namespace`](Self::with_namespace) but yields `None` instead of
+	/// an error when the namespace does not exist.
+	pub fn maybe_with_namespace<F, R>(&self, namespace: &str, f: F) -> Option<R>
+	where
+		F: FnOnce(&NamespaceMap) -> R,
+	{
+		let lock = self.namespaces.get(&Arc::new(namespace.to_string()))?;
+		let guard = lock.read().ok()?;
+		Some(f(&guard))
+	}
+
+	/// Runs `f` against a write-locked view of `namespace`, allowing in-place
+	/// mutation of one namespace without blocking readers of the others.
+	pub fn with_namespace_mut<F, R>(&self, namespace: &str, f: F) -> Result<R, RuntimeError>
+	where
+		F: FnOnce(&mut NamespaceMap) -> R,
+	{
+		match self.namespaces.get(&Arc::new(namespace.to_string())) {
+			Some(lock) => {
+				let mut guard = lock.write().map_err(|_| {
+					RuntimeError::CustomFunctionError(format!("namespace `{}` lock poisoned", namespace))
+				})?;
+				Ok(f(&mut guard))
+			}
+			None => Err(RuntimeError::NamespaceNotFound(namespace.to_string())),
+		}
+	}
+
+	/// Reads a single variable, cloning its `Arc` handle.
+	pub fn get_value(&self, namespace: &str, variable: &str) -> Result<Arc<Value>, RuntimeError> {
+		self.with_namespace(namespace, |ns| {
+			ns.get(&Arc::new(variable.to_string())).cloned()
+		})?
+		.ok_or_else(|| RuntimeError::VariableNotFound(variable.to_string()))
+	}
+
+	/// Inserts or overwrites a variable in an existing namespace via a shared
+	/// `&self`, contending only with other readers and writers of the same
+	/// namespace.
+	pub fn add_value(&self, namespace: &str, key: &str, value: Value) -> Result<(), RuntimeError> {
+		self.with_namespace_mut(namespace, |ns| {
+			ns.insert(Arc::new(key.to_string()), Arc::new(value));
+		})
+	}
+
+	/// Updates an existing variable, erroring if it is absent.
+	pub fn update_value(&self, namespace: &str, key: &str, value: Value) -> Result<(), RuntimeError> {
+		self.with_namespace_mut(namespace, |ns| {
+			let key = Arc::new(key.to_string());
+			if ns.contains_key(&key) {
+				ns.insert(key, Arc::new(value));
+				Ok(())
+			} else {
+				Err(RuntimeError::VariableNotFound(key.to_string()))
+			}
+		})?
+	}
+
+	/// Removes a variable, erroring if it is absent.
+	pub fn delete_value(&self, namespace: &str, key: &str) -> Result<(), RuntimeError> {
+		self.with_namespace_mut(namespace, |ns| {
+			if ns.remove(&Arc::new(key.to_string())).is_some() {
+				Ok(())
+			} else {
+				Err(RuntimeError::VariableNotFound(key.to_string()))
+			}
+		})?
+	}
+
+	/// Creates a new empty namespace. Adding a namespace changes the outer map's
+	/// shape and so requires `&mut self`.
+	pub fn add_namespace(&mut self, namespace: &str) -> Result<(), RuntimeError> {
+		let namespace = Arc::new(namespace.to_string());
+		if self.namespaces.contains_key(&namespace) {
+			Err(RuntimeError::NamespaceAlreadyExists(namespace.to_string()))
+		} else {
+			self.namespaces.insert(namespace, RwLock::new(FnvHashMap::default()));
+			Ok(())
+		}
+	}
+
+	/// Removes an entire namespace. Like [`add_namespace`](Self::add_namespace),
+	/// this reshapes the outer map and requires `&mut self`.
+	pub fn delete_namespace(&mut self, namespace: &str) -> Result<(), RuntimeError> {
+		let namespace = Arc::new(namespace.to_string());
+		if self.namespaces.remove(&namespace).is_some() {
+			Ok(())
+		} else {
+			Err(RuntimeError::NamespaceNotFound(namespace.to_string()))
+		}
+	}
+
+	/// Returns the names of all loaded namespaces.
+	pub fn namespace_names(&self) -> Vec<Arc<String>> {
+		self.namespaces.keys().cloned().collect()
+	}
+}
+
+impl From<Runtime> for ConcurrentRuntime {
+	fn from(runtime: Runtime) -> Self {
+		ConcurrentRuntime::from_runtime(runtime)
+	}
+}
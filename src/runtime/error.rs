@@ -1,11 +1,16 @@
 use fmt::Display;
 use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
 
 /// Represents all possible runtime errors.
 #[derive(Debug)]
 pub enum RuntimeError {
-	CircularReference,
+	/// A reference cycle was detected during resolution. The payload is the
+	/// ordered `(namespace, variable)` path that was being resolved, ending with
+	/// the reference that closed the loop, so callers can render the chain
+	/// without enabling global tracing.
+	CircularReference(Vec<(Arc<String>, Arc<String>)>),
 	IndexOutOfBounds(usize),
 	InvalidAccessor(String),
 	InvalidRange(usize, usize),
@@ -16,12 +21,14 @@ pub enum RuntimeError {
 	TypeError(String),
 	UnknownIntrinsic(String),
 	InvalidIntrinsicArgs,
+	UnwrapNil,
 	IntrinsicTypeMismatch(String),
 	ConversionError(String),
 	NamespaceNotFound(String),
 	VariableNotFound(String),
 	NamespaceAlreadyExists(String),
 	CustomFunctionError(String),
+	ValidationErrors(Vec<String>),
 	AnyhowError(anyhow::Error),
 }
 
@@ -38,7 +45,18 @@ impl Display for RuntimeError {
 			RuntimeError::ParseError(msg) => write!(f, "Parse error: {}", msg),
 			RuntimeError::NamespaceNotFound(name) => write!(f, "Namespace not found: {}", name),
 			RuntimeError::VariableNotFound(name) => write!(f, "Variable not found: {}", name),
-			RuntimeError::CircularReference => write!(f, "Circular reference detected"),
+			RuntimeError::CircularReference(path) => {
+				write!(f, "Circular reference detected")?;
+				if !path.is_empty() {
+					let chain = path
+						.iter()
+						.map(|(ns, var)| format!("{}.{}", ns, var))
+						.collect::<Vec<_>>()
+						.join(" -> ");
+					write!(f, ": {}", chain)?;
+				}
+				Ok(())
+			}
 			RuntimeError::MissingNamespace => write!(f, "Missing namespace"),
 			RuntimeError::IndexOutOfBounds(index) => write!(f, "Index out of bounds: {}", index),
 			RuntimeError::InvalidRange(start, end) => write!(f, "Invalid range: {} to {}", start, end),
@@ -48,9 +66,21 @@ impl Display for RuntimeError {
 			RuntimeError::UnknownIntrinsic(name) => write!(f, "Unknown intrinsic: {}", name),
 			RuntimeError::NoNamespaces => write!(f, "No namespaces found"),
 			RuntimeError::InvalidIntrinsicArgs => write!(f, "Invalid number of intrinsic arguments"),
+			RuntimeError::UnwrapNil => write!(f, "Attempted to unwrap a Nil value"),
 			RuntimeError::NamespaceAlreadyExists(name) => write!(f, "Namespace already exists: {}", name),
 			RuntimeError::IntrinsicTypeMismatch(argtype) => write!(f, "Invalid intrinsic argument. Data type mismatch error: {}", argtype),
 			RuntimeError::CustomFunctionError(funcname) => write!(f, "Custom function error: {}", funcname),
+			RuntimeError::ValidationErrors(errors) => {
+				writeln!(f, "Namespace validation failed ({} issue(s)):", errors.len())?;
+				for (i, err) in errors.iter().enumerate() {
+					if i + 1 < errors.len() {
+						writeln!(f, "  - {}", err)?;
+					} else {
+						write!(f, "  - {}", err)?;
+					}
+				}
+				Ok(())
+			},
 			RuntimeError::AnyhowError(err) => write!(f, "External error: {}", err),
 		}
 	}
@@ -32,6 +32,7 @@
 //! let value = runtime.get_value("namespace", "variable", &[]).unwrap();
 //! ```
 
+use ::std::cell::RefCell;
 use ::std::{fmt, fs};
 use ::std::path::PathBuf;
 use ::std::sync::Arc;
@@ -42,12 +43,17 @@ use smallvec::SmallVec;
 use crate::parser::parse_vtc;
 use crate::runtime::error::RuntimeError;
 use crate::runtime::std::StdLibLoader;
-use crate::value::{Accessor, Number, Reference, ReferenceType, Value, VtcFile};
+use crate::bignum::BigInt;
+use crate::value::{Accessor, BinaryOp, Expr, Number, Reference, ReferenceType, UnaryOp, Value, VtcFile};
 
 pub mod runtime;
 pub mod error;
 pub mod std;
 pub mod serialize;
+pub mod de;
+pub mod binary;
+pub mod query;
+pub mod concurrent;
 mod memory;
 mod utils;
 
@@ -70,7 +76,87 @@ mod utils;
 /// * `std_lib_loader` - Loader for standard library functions
 pub struct Runtime {
 	pub namespaces: FnvHashMap<Arc<String>, FnvHashMap<Arc<String>, Arc<Value>>>,
-	std_lib_loader: StdLibLoader
+	std_lib_loader: StdLibLoader,
+	intrinsics: FnvHashMap<String, CustomIntrinsic>,
+	/// Memoized reference resolutions. Interior mutability so `resolve_reference`
+	/// can populate it through `&self`; evicted on any mutation.
+	cache: RefCell<ResolutionCache>,
+	/// Dependency accumulator for the in-flight top-level resolution, if any.
+	current_deps: RefCell<Option<FnvHashSet<(Arc<String>, Arc<String>)>>>,
+	/// Whether resolution tracing is enabled, read once from
+	/// `VTC_TRACE_RESOLUTION` at construction time.
+	trace: bool,
+	/// Ordered stack of references currently being resolved, maintained so a
+	/// detected cycle can report the full chain that produced it.
+	trace_stack: RefCell<Vec<(Arc<String>, Arc<String>)>>,
+}
+
+/// A host-registered intrinsic: a fallible function over already-resolved
+/// argument values, consulted before the built-in `std_*` table.
+pub type CustomIntrinsic = Box<dyn Fn(&[Value]) -> Result<Value, RuntimeError> + Send + Sync>;
+
+/// A fully-qualified, resolved lookup: the concrete namespace, the variable and
+/// the accessor chain applied to it. Used as the [`ResolutionCache`] key.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+	namespace: Arc<String>,
+	variable: Arc<String>,
+	accessors: Vec<Accessor>,
+}
+
+/// Caches resolved values keyed by [`CacheKey`], with a reverse dependency index
+/// so a mutation to any `(namespace, variable)` can evict exactly the entries
+/// that transitively depended on it.
+#[derive(Default)]
+struct ResolutionCache {
+	entries: FnvHashMap<CacheKey, Arc<Value>>,
+	/// Maps a `(namespace, variable)` to every cache entry that read it while
+	/// resolving.
+	dependents: FnvHashMap<(Arc<String>, Arc<String>), Vec<CacheKey>>,
+}
+
+impl ResolutionCache {
+	fn get(&self, key: &CacheKey) -> Option<Arc<Value>> {
+		self.entries.get(key).cloned()
+	}
+
+	fn insert(&mut self, key: CacheKey, value: Arc<Value>, deps: FnvHashSet<(Arc<String>, Arc<String>)>) {
+		for dep in deps {
+			self.dependents.entry(dep).or_default().push(key.clone());
+		}
+		self.entries.insert(key, value);
+	}
+
+	/// Evicts every entry that depended on the mutated `(namespace, variable)`.
+	fn invalidate_key(&mut self, namespace: &Arc<String>, variable: &Arc<String>) {
+		if let Some(keys) = self.dependents.remove(&(Arc::clone(namespace), Arc::clone(variable))) {
+			for key in keys {
+				self.entries.remove(&key);
+			}
+		}
+	}
+
+	/// Evicts every entry that depended on any variable in `namespace`.
+	fn invalidate_namespace(&mut self, namespace: &Arc<String>) {
+		let affected: Vec<_> = self
+			.dependents
+			.keys()
+			.filter(|(ns, _)| ns == namespace)
+			.cloned()
+			.collect();
+		for dep in affected {
+			if let Some(keys) = self.dependents.remove(&dep) {
+				for key in keys {
+					self.entries.remove(&key);
+				}
+			}
+		}
+	}
+
+	fn clear(&mut self) {
+		self.entries.clear();
+		self.dependents.clear();
+	}
 }
 
 impl fmt::Debug for Runtime {
@@ -91,9 +177,29 @@ impl Runtime {
 		Runtime {
 			namespaces: FnvHashMap::default(),
 			std_lib_loader: StdLibLoader::new(),
+			intrinsics: FnvHashMap::default(),
+			cache: RefCell::new(ResolutionCache::default()),
+			current_deps: RefCell::new(None),
+			trace: ::std::env::var("VTC_TRACE_RESOLUTION")
+				.map(|v| v == "1")
+				.unwrap_or(false),
+			trace_stack: RefCell::new(Vec::new()),
 		}
 	}
 
+	/// Registers a host-provided intrinsic callable as `[name!!, arg, ...]` from
+	/// within `.vtc` files. Registered intrinsics are consulted before the
+	/// built-in `std_*` table, so domain functions like `env_var!!`, `clamp!!`,
+	/// or `now!!` behave exactly like the built-ins — including nested evaluation
+	/// (their arguments are fully resolved first). An unknown `!!` name still
+	/// surfaces [`RuntimeError::UnknownIntrinsic`].
+	pub fn register_intrinsic<F>(&mut self, name: &str, f: F)
+	where
+		F: Fn(&[Value]) -> Result<Value, RuntimeError> + Send + Sync + 'static,
+	{
+		self.intrinsics.insert(name.to_string(), Box::new(f));
+	}
+
 	/// Creates a runtime environment from a VTC file at the specified path.
 	///
 	/// # Arguments
@@ -196,6 +302,8 @@ impl Runtime {
 	}
 
 	fn load_vtc_file(&mut self, vtc_file: VtcFile) -> Result<(), RuntimeError> {
+		let mut issues: Vec<String> = Vec::new();
+
 		for namespace in vtc_file.namespaces {
 			let mut variables = FnvHashMap::with_capacity_and_hasher(
 				namespace.variables.len(),
@@ -205,12 +313,63 @@ impl Runtime {
 			for var in namespace.variables {
 				let key = Arc::new(var.name);
 				let value = Arc::new(var.value);
+				// A later binding of the same name silently overrode earlier ones;
+				// flag the override up front when it changes the value's type.
+				if let Some(existing) = variables.get(&key) {
+					if value_type_name(existing) != value_type_name(&value) {
+						issues.push(format!(
+							"{}.{}: conflicting redefinition overrides a {} with a {}",
+							namespace.name,
+							key,
+							value_type_name(existing),
+							value_type_name(&value),
+						));
+					}
+				}
 				variables.insert(key, value);
 			}
 
 			self.namespaces.insert(Arc::new(namespace.name), variables);
 		}
-		Ok(())
+
+		self.cache.borrow_mut().clear();
+		self.validate_references(&mut issues);
+
+		if issues.is_empty() {
+			Ok(())
+		} else {
+			Err(RuntimeError::ValidationErrors(issues))
+		}
+	}
+
+	/// Eagerly resolves every inherited reference target against the loaded
+	/// namespaces, appending a message for each one that points at a missing
+	/// namespace or variable. This turns what would otherwise be a lazy, partial
+	/// failure at `get_value` time into a complete report at load time.
+	///
+	/// Resolution goes through [`Runtime::lookup_with_fallback`], the same
+	/// dotted-namespace parent fallback `get_value` and the schema validator
+	/// use, so a reference that only resolves via an ancestor namespace isn't
+	/// flagged as broken.
+	fn validate_references(&self, issues: &mut Vec<String>) {
+		for (namespace, variables) in &self.namespaces {
+			for (var_name, value) in variables {
+				let mut refs = Vec::new();
+				collect_references(value, &mut refs);
+				for reference in refs {
+					let target_ns = reference.namespace.clone().unwrap_or_else(|| namespace.clone());
+
+					let resolved = self.lookup_with_fallback(&target_ns, &reference.variable).is_some();
+
+					if !resolved {
+						issues.push(format!(
+							"{}.{}: inherited name `{}.{}` does not resolve",
+							namespace, var_name, target_ns, reference.variable,
+						));
+					}
+				}
+			}
+		}
 	}
 
 	pub fn update_library_loader(&mut self, lib_loader: StdLibLoader) -> Result<(), RuntimeError> {
@@ -218,6 +377,22 @@ impl Runtime {
 		Ok(())
 	}
 
+	/// Registers a custom evaluator function on the runtime's standard library loader.
+	///
+	/// This makes `[name!!, arg, ...]` intrinsic calls dispatch to the supplied
+	/// closure, exactly like the built-in `std_*` functions. Names starting with
+	/// `std` are reserved for the builtin set.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The intrinsic name (without the `!!` suffix)
+	/// * `function` - The evaluator to install
+	pub fn register_function(&mut self, name: String, function: crate::runtime::std::VtcFn) -> Result<(), RuntimeError> {
+		self.std_lib_loader
+			.register_function(name, function)
+			.map_err(RuntimeError::from)
+	}
+
 	/// Retrieves a value from the runtime.
 	///
 	/// # Arguments
@@ -247,6 +422,25 @@ impl Runtime {
 		self.resolve_reference(&reference)
 	}
 
+	/// Resolves a reference, returning `Value::Nil` instead of an error when the
+	/// target is simply absent (missing variable, out-of-range index, missing
+	/// key). Errors that indicate a malformed config — circular references,
+	/// intrinsic failures — still propagate. This pairs with the optional `->?`
+	/// navigation accessor so defaults flow naturally.
+	pub fn get_value_with_ref(&self, reference: &Reference) -> Result<Arc<Value>, RuntimeError> {
+		match self.resolve_reference(reference) {
+			Ok(v) => Ok(v),
+			Err(
+				RuntimeError::VariableNotFound(_)
+				| RuntimeError::NamespaceNotFound(_)
+				| RuntimeError::IndexOutOfBounds(_)
+				| RuntimeError::InvalidRange(_, _)
+				| RuntimeError::InvalidAccessor(_),
+			) => Ok(Arc::new(Value::Nil)),
+			Err(e) => Err(e),
+		}
+	}
+
 	/// Adds a new value to the specified namespace.
 	///
 	/// If the namespace doesn't exist, it will be created automatically.
@@ -264,6 +458,7 @@ impl Runtime {
 		let namespace = Arc::new(namespace.to_string());
 		let key = Arc::new(key.to_string());
 
+		self.cache.borrow_mut().invalidate_key(&namespace, &key);
 		self.namespaces
 			.entry(namespace)
 			.or_insert_with(FnvHashMap::default)
@@ -296,7 +491,8 @@ impl Runtime {
 		match self.namespaces.get_mut(&namespace) {
 			Some(ns) => {
 				if ns.contains_key(&key) {
-					ns.insert(key, Arc::new(value));
+					ns.insert(Arc::clone(&key), Arc::new(value));
+					self.cache.borrow_mut().invalidate_key(&namespace, &key);
 					Ok(())
 				} else {
 					Err(RuntimeError::VariableNotFound(key.to_string()))
@@ -329,6 +525,7 @@ impl Runtime {
 		match self.namespaces.get_mut(&namespace) {
 			Some(ns) => {
 				if ns.remove(&key).is_some() {
+					self.cache.borrow_mut().invalidate_key(&namespace, &key);
 					Ok(())
 				} else {
 					Err(RuntimeError::VariableNotFound(key.to_string()))
@@ -340,6 +537,11 @@ impl Runtime {
 
 	/// Creates a new empty namespace.
 	///
+	/// A dotted name defines a path in the namespace hierarchy
+	/// (`app.db.pool`); any intermediate parents that do not yet exist are
+	/// created automatically as empty namespaces, so `add_namespace("app.db.pool")`
+	/// also materialises `app` and `app.db`.
+	///
 	/// # Arguments
 	///
 	/// * `namespace` - The name of the namespace to create
@@ -354,12 +556,23 @@ impl Runtime {
 	pub fn add_namespace(&mut self, namespace: &str) -> Result<(), RuntimeError> {
 		let namespace = Arc::new(namespace.to_string());
 
-		if !self.namespaces.contains_key(&namespace) {
-			self.namespaces.insert(namespace, FnvHashMap::default());
-			Ok(())
-		} else {
-			Err(RuntimeError::NamespaceAlreadyExists(namespace.to_string()))
+		if self.namespaces.contains_key(&namespace) {
+			return Err(RuntimeError::NamespaceAlreadyExists(namespace.to_string()));
+		}
+
+		// Auto-create any missing ancestors so the hierarchy is always contiguous.
+		let mut prefix = String::new();
+		for segment in namespace.split('.') {
+			if !prefix.is_empty() {
+				prefix.push('.');
+			}
+			prefix.push_str(segment);
+			self.namespaces
+				.entry(Arc::new(prefix.clone()))
+				.or_insert_with(FnvHashMap::default);
 		}
+
+		Ok(())
 	}
 
 	/// Removes an entire namespace and all its variables.
@@ -379,6 +592,7 @@ impl Runtime {
 		let namespace = Arc::new(namespace.to_string());
 
 		if self.namespaces.remove(&namespace).is_some() {
+			self.cache.borrow_mut().invalidate_namespace(&namespace);
 			Ok(())
 		} else {
 			Err(RuntimeError::NamespaceNotFound(namespace.to_string()))
@@ -395,6 +609,33 @@ impl Runtime {
 		self.namespaces.keys().collect()
 	}
 
+	/// Returns every namespace that lives under `prefix` in the dotted
+	/// hierarchy, including `prefix` itself when it exists.
+	///
+	/// A namespace `name` is considered under `prefix` when it equals `prefix`
+	/// or begins with `prefix` followed by a `.` separator, so
+	/// `list_namespaces_under("app")` yields `app`, `app.db`, `app.db.pool`
+	/// but never an unrelated `application`.
+	///
+	/// # Arguments
+	///
+	/// * `prefix` - The ancestor path to filter by
+	///
+	/// # Returns
+	///
+	/// A vector of references to the matching namespace names
+	pub fn list_namespaces_under(&self, prefix: &str) -> Vec<&Arc<String>> {
+		self.namespaces
+			.keys()
+			.filter(|name| {
+				name.as_str() == prefix
+					|| name.len() > prefix.len()
+						&& name.starts_with(prefix)
+						&& name.as_bytes()[prefix.len()] == b'.'
+			})
+			.collect()
+	}
+
 
 	/// Lists all variables in a specified namespace.
 	///
@@ -432,9 +673,35 @@ impl Runtime {
 	/// * Circular references are detected
 	/// * Referenced namespace or variable doesn't exist
 	/// * Invalid accessors are encountered
-	fn resolve_reference(&self, reference: &Reference) -> Result<Arc<Value>, RuntimeError> {
+	pub(crate) fn resolve_reference(&self, reference: &Reference) -> Result<Arc<Value>, RuntimeError> {
+		// Only references with a concrete namespace are cacheable; an unqualified
+		// reference has no stable key. For those, resolve without memoization.
+		let cache_key = reference.namespace.as_ref().map(|ns| CacheKey {
+			namespace: Arc::clone(ns),
+			variable: Arc::clone(&reference.variable),
+			accessors: reference.accessors.to_vec(),
+		});
+
+		if let Some(key) = &cache_key {
+			if let Some(hit) = self.cache.borrow().get(key) {
+				return Ok(hit);
+			}
+		}
+
+		// Track every `(namespace, variable)` touched during this resolution so
+		// the result can be evicted when any of them is later mutated.
 		let mut visited = FnvHashSet::default();
-		self.resolve_reference_recursive(reference, &mut visited, reference.namespace.clone())
+		*self.current_deps.borrow_mut() = Some(FnvHashSet::default());
+		self.trace_stack.borrow_mut().clear();
+		let result =
+			self.resolve_reference_recursive(reference, &mut visited, reference.namespace.clone());
+		let deps = self.current_deps.borrow_mut().take();
+
+		if let (Ok(value), Some(key), Some(deps)) = (&result, cache_key, deps) {
+			self.cache.borrow_mut().insert(key, Arc::clone(value), deps);
+		}
+
+		result
 	}
 
 	/// Recursively resolves a reference while tracking visited references to prevent cycles.
@@ -458,15 +725,53 @@ impl Runtime {
 			.ok_or_else(|| RuntimeError::MissingNamespace)?;
 
 		let key = (Arc::clone(namespace), Arc::clone(&reference.variable));
+		// Record the dependency for cache invalidation before the cycle check, so
+		// the accumulator sees every variable read during resolution.
+		if let Some(deps) = self.current_deps.borrow_mut().as_mut() {
+			deps.insert(key.clone());
+		}
 		if !visited.insert(key.clone()) {
-			return Err(RuntimeError::CircularReference);
+			let mut path = self.trace_stack.borrow().clone();
+			path.push(key.clone());
+			if self.trace {
+				eprintln!(
+					"[vtc trace] circular reference: {}",
+					format_trace_path(&path),
+				);
+			}
+			return Err(RuntimeError::CircularReference(path));
 		}
 
-		let variables = self.namespaces.get(namespace)
-			.ok_or_else(|| RuntimeError::NamespaceNotFound(namespace.to_string()))?;
-		let mut value = variables.get(&reference.variable)
-			.ok_or_else(|| RuntimeError::VariableNotFound(reference.variable.to_string()))?
-			.clone();
+		self.trace_stack.borrow_mut().push(key.clone());
+		if self.trace {
+			eprintln!(
+				"[vtc trace] resolve {}.{} (depth {})",
+				namespace,
+				reference.variable,
+				self.trace_stack.borrow().len(),
+			);
+		}
+
+		// Walk the dotted hierarchy, falling back to parent scopes: a lookup in
+		// `app.db.pool` that misses retries against `app.db`, then `app`, so a
+		// child namespace inherits (and may override) its parents' defaults.
+		let (found_ns, mut value) = self
+			.lookup_with_fallback(namespace, &reference.variable)
+			.ok_or_else(|| {
+				if self.namespace_chain_exists(namespace) {
+					RuntimeError::VariableNotFound(reference.variable.to_string())
+				} else {
+					RuntimeError::NamespaceNotFound(namespace.to_string())
+				}
+			})?;
+
+		// The value may have come from an ancestor; record that namespace too so a
+		// mutation there evicts any cache entry that inherited it.
+		if !Arc::ptr_eq(&found_ns, namespace) {
+			if let Some(deps) = self.current_deps.borrow_mut().as_mut() {
+				deps.insert((Arc::clone(&found_ns), Arc::clone(&reference.variable)));
+			}
+		}
 
 		value = self.resolve_value(value, visited)?;
 		value = self.resolve_intrinsics(value, visited)?;
@@ -476,9 +781,67 @@ impl Runtime {
 		}
 
 		visited.remove(&key);
+		self.trace_stack.borrow_mut().pop();
 		Ok(value)
 	}
 
+	/// Looks a variable up in `namespace`, then walks up the dotted hierarchy
+	/// (`app.db.pool` → `app.db` → `app`) until the variable is found. Returns
+	/// the ancestor namespace the value was actually read from alongside it, or
+	/// `None` when no scope in the chain binds the name.
+	fn lookup_with_fallback(
+		&self,
+		namespace: &Arc<String>,
+		variable: &Arc<String>,
+	) -> Option<(Arc<String>, Arc<Value>)> {
+		let mut current = Some(Arc::clone(namespace));
+		while let Some(ns) = current {
+			if let Some(value) = self.namespaces.get(&ns).and_then(|vars| vars.get(variable)) {
+				return Some((ns, value.clone()));
+			}
+			current = parent_namespace(&ns);
+		}
+		None
+	}
+
+	/// Builds the effective variable set for `namespace`, folding in every
+	/// variable inherited from its dotted-path ancestors (`app.db.pool` also
+	/// sees `app.db` and `app`'s bindings) the same way [`Runtime::lookup_with_fallback`]
+	/// resolves a single name. Ancestors are merged outermost-first, so a
+	/// variable redeclared closer to `namespace` wins.
+	pub(crate) fn effective_variables(&self, namespace: &Arc<String>) -> FnvHashMap<Arc<String>, Arc<Value>> {
+		let mut chain = Vec::new();
+		let mut current = Some(Arc::clone(namespace));
+		while let Some(ns) = current {
+			chain.push(ns.clone());
+			current = parent_namespace(&ns);
+		}
+
+		let mut merged = FnvHashMap::default();
+		for ns in chain.into_iter().rev() {
+			if let Some(vars) = self.namespaces.get(&ns) {
+				for (name, value) in vars {
+					merged.insert(name.clone(), value.clone());
+				}
+			}
+		}
+		merged
+	}
+
+	/// Reports whether any namespace in the dotted chain rooted at `namespace`
+	/// exists, so resolution can distinguish a missing variable from a wholly
+	/// unknown namespace path.
+	pub(crate) fn namespace_chain_exists(&self, namespace: &Arc<String>) -> bool {
+		let mut current = Some(Arc::clone(namespace));
+		while let Some(ns) = current {
+			if self.namespaces.contains_key(&ns) {
+				return true;
+			}
+			current = parent_namespace(&ns);
+		}
+		false
+	}
+
 	fn resolve_value(
 		&self,
 		value: Arc<Value>,
@@ -511,10 +874,140 @@ impl Runtime {
 					))))
 				}
 			}
+			Value::Map(entries) => {
+				let resolved = entries
+					.iter()
+					.map(|(key, val)| {
+						self.resolve_value(Arc::new(val.clone()), visited)
+							.map(|arc| (Arc::clone(key), (*arc).clone()))
+					})
+					.collect::<Result<Vec<_>, _>>()?;
+				Ok(Arc::new(Value::Map(Arc::new(resolved))))
+			}
+			Value::Expr(expr) => self.eval_expr(expr, visited),
 			_ => Ok(value),
 		}
 	}
 
+	/// Evaluates a computed expression, resolving any references in its operands
+	/// through the normal resolution pass before applying the operator.
+	fn eval_expr(
+		&self,
+		expr: &Expr,
+		visited: &mut FnvHashSet<(Arc<String>, Arc<String>)>,
+	) -> Result<Arc<Value>, RuntimeError> {
+		match expr {
+			Expr::Unary { op, operand } => {
+				let operand = self.resolve_value(Arc::new(operand.clone()), visited)?;
+				match op {
+					UnaryOp::Neg => match as_number(&operand)? {
+						Number::Float(f) => Ok(Arc::new(Value::Number(Number::Float(-f)))),
+						Number::BigInt(b) => {
+							Ok(Arc::new(Value::Number(Number::from_bigint(b.neg()))))
+						}
+						ref n => {
+							let i = number_as_i64(n);
+							let neg = i.checked_neg().map(Number::Integer).unwrap_or_else(|| {
+								Number::from_bigint(BigInt::from_i64(i).neg())
+							});
+							Ok(Arc::new(Value::Number(neg)))
+						}
+					},
+					UnaryOp::Not => {
+						let b = operand.as_bool().ok_or_else(|| {
+							RuntimeError::TypeError("`!` expects a boolean".to_string())
+						})?;
+						Ok(Arc::new(Value::Boolean(!b)))
+					}
+				}
+			}
+			Expr::Binary { op, lhs, rhs } => {
+				let lhs = self.resolve_value(Arc::new(lhs.clone()), visited)?;
+				let rhs = self.resolve_value(Arc::new(rhs.clone()), visited)?;
+				self.apply_binary_op(*op, &lhs, &rhs)
+			}
+		}
+	}
+
+	fn apply_binary_op(
+		&self,
+		op: BinaryOp,
+		lhs: &Arc<Value>,
+		rhs: &Arc<Value>,
+	) -> Result<Arc<Value>, RuntimeError> {
+		if let BinaryOp::Concat = op {
+			let l = lhs
+				.as_string()
+				.ok_or_else(|| RuntimeError::TypeError("`++` expects strings".to_string()))?;
+			let r = rhs
+				.as_string()
+				.ok_or_else(|| RuntimeError::TypeError("`++` expects strings".to_string()))?;
+			return Ok(Arc::new(Value::String(format!("{}{}", l, r))));
+		}
+
+		let ln = as_number(lhs)?;
+		let rn = as_number(rhs)?;
+
+		// Promote to float if either operand is a float; otherwise stay integer.
+		let float_mode = matches!(ln, Number::Float(_)) || matches!(rn, Number::Float(_));
+
+		match op {
+			BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Eq => {
+				let (l, r) = (number_as_f64(&ln), number_as_f64(&rn));
+				let result = match op {
+					BinaryOp::Lt => l < r,
+					BinaryOp::Gt => l > r,
+					BinaryOp::Eq => l == r,
+					_ => unreachable!(),
+				};
+				Ok(Arc::new(Value::Boolean(result)))
+			}
+			_ if float_mode => {
+				let (l, r) = (number_as_f64(&ln), number_as_f64(&rn));
+				let result = match op {
+					BinaryOp::Add => l + r,
+					BinaryOp::Sub => l - r,
+					BinaryOp::Mul => l * r,
+					BinaryOp::Div => l / r,
+					_ => unreachable!(),
+				};
+				Ok(Arc::new(Value::Number(Number::Float(result))))
+			}
+			BinaryOp::Div => {
+				let (l, r) = (number_as_i64(&ln), number_as_i64(&rn));
+				if r == 0 {
+					return Err(RuntimeError::ConversionError("division by zero".to_string()));
+				}
+				Ok(Arc::new(Value::Number(Number::Integer(l / r))))
+			}
+			_ => {
+				// Stay in `i64` on the fast path, promoting to `BigInt` only when
+				// the checked op reports overflow.
+				let (l, r) = (number_as_i64(&ln), number_as_i64(&rn));
+				let checked = match op {
+					BinaryOp::Add => l.checked_add(r),
+					BinaryOp::Sub => l.checked_sub(r),
+					BinaryOp::Mul => l.checked_mul(r),
+					_ => unreachable!(),
+				};
+				let result = match checked {
+					Some(v) => Number::Integer(v),
+					None => {
+						let (bl, br) = (BigInt::from_i64(l), BigInt::from_i64(r));
+						let big = match op {
+							BinaryOp::Add => bl.add(&br),
+							BinaryOp::Sub => bl.sub(&br),
+							BinaryOp::Mul => bl.mul(&br),
+							_ => unreachable!(),
+						};
+						Number::from_bigint(big)
+					}
+				};
+				Ok(Arc::new(Value::Number(result)))
+			}
+		}
+	}
+
 	/// Applies an accessor to a value (e.g., list indexing or string slicing).
 	///
 	/// # Arguments
@@ -546,6 +1039,29 @@ impl Runtime {
 					Ok(Arc::new(Value::List(Arc::new(list[*start..*end].to_vec()))))
 				}
 			}
+			(Value::List(list), Accessor::IndexFromEnd(n)) => {
+				// `(-1)` is the last element; erroring only if it runs off the front.
+				list.len()
+					.checked_sub(*n)
+					.and_then(|idx| list.get(idx))
+					.map(|v| Arc::new(v.clone()))
+					.ok_or(RuntimeError::IndexOutOfBounds(*n))
+			}
+			(Value::List(list), Accessor::RangeFrom(start)) => {
+				if *start > list.len() {
+					Err(RuntimeError::InvalidRange(*start, list.len()))
+				} else {
+					Ok(Arc::new(Value::List(Arc::new(list[*start..].to_vec()))))
+				}
+			}
+			(Value::List(list), Accessor::RangeTo(end)) => {
+				// Clamp an out-of-bounds end instead of erroring.
+				let end = (*end).min(list.len());
+				Ok(Arc::new(Value::List(Arc::new(list[..end].to_vec()))))
+			}
+			(Value::List(list), Accessor::RangeFull) => {
+				Ok(Arc::new(Value::List(Arc::new(list.to_vec()))))
+			}
 			(Value::String(s), Accessor::Index(index)) => {
 				s.chars()
 					.nth(*index)
@@ -559,6 +1075,25 @@ impl Runtime {
 					Ok(Arc::new(Value::String(s[*start..*end].to_string())))
 				}
 			}
+			(_, Accessor::Optional(inner)) => {
+				// Swallow "absence" errors into Nil; propagate everything else.
+				match self.apply_accessor(value, inner) {
+					Ok(v) => Ok(v),
+					Err(
+						RuntimeError::IndexOutOfBounds(_)
+						| RuntimeError::InvalidRange(_, _)
+						| RuntimeError::InvalidAccessor(_)
+						| RuntimeError::VariableNotFound(_),
+					) => Ok(Arc::new(Value::Nil)),
+					Err(e) => Err(e),
+				}
+			}
+			(Value::Map(map), Accessor::Key(key)) => {
+				map.iter()
+					.find(|(k, _)| k.as_str() == key)
+					.map(|(_, v)| Arc::new(v.clone()))
+					.ok_or_else(|| RuntimeError::VariableNotFound(key.clone()))
+			}
 			(_, Accessor::Key(key)) => {
 				Err(RuntimeError::InvalidAccessor(format!(
 					"Key accessor '{}' not supported for this value type",
@@ -589,9 +1124,55 @@ impl Runtime {
 		match &*value {
 			Value::List(items) => {
 				if let Some(Value::Intrinsic(name)) = items.first() {
+					// `unwrap!!` is resolved directly so it can raise a dedicated
+					// error on Nil, giving authors a checked "must be present" marker.
+					if name == "unwrap" {
+						let args = self.collect_intrinsic_args(items, visited)?;
+						if args.len() != 1 {
+							return Err(RuntimeError::InvalidIntrinsicArgs);
+						}
+						return if matches!(&*args[0], Value::Nil) {
+							Err(RuntimeError::UnwrapNil)
+						} else {
+							Ok(args[0].clone())
+						};
+					}
+					// `std_try` recovers from a failing expression. It must be
+					// intercepted here, before the generic path eagerly resolves
+					// its arguments, so a `RuntimeError` raised while evaluating
+					// the first argument can be caught and the default substituted.
+					if name == "std_try" {
+						if items.len() != 3 {
+							return Err(RuntimeError::InvalidIntrinsicArgs);
+						}
+						// A failed resolution leaves its references in `visited` and
+						// on the trace stack; restore both before trying the default.
+						let saved_visited = visited.clone();
+						let saved_depth = self.trace_stack.borrow().len();
+						return match self.resolve_value(Arc::new(items[1].clone()), visited) {
+							Ok(value) => Ok(value),
+							Err(_) => {
+								*visited = saved_visited;
+								self.trace_stack.borrow_mut().truncate(saved_depth);
+								self.resolve_value(Arc::new(items[2].clone()), visited)
+							}
+						};
+					}
+					// Host-registered intrinsics take precedence over the builtins.
+					if let Some(custom) = self.intrinsics.get(name.as_str()) {
+						let args = self.collect_intrinsic_args(items, visited)?;
+						if self.trace {
+							self.trace_intrinsic(name, &args);
+						}
+						let arg_values: Vec<Value> = args.iter().map(|a| (**a).clone()).collect();
+						return custom(&arg_values).map(Arc::new);
+					}
 					if let Some(func) = self.std_lib_loader.get_function(name) {
 						let args = self.validate_and_collect_args(name, items, visited)?;
-						Ok(func(args))
+						if self.trace {
+							self.trace_intrinsic(name, &args);
+						}
+						func(args)
 					} else {
 						Err(RuntimeError::UnknownIntrinsic(name.clone()))
 					}
@@ -606,6 +1187,16 @@ impl Runtime {
 					Ok(Arc::new(Value::List(Arc::new(resolved_items))))
 				}
 			},
+			Value::Map(entries) => {
+				let resolved = entries
+					.iter()
+					.map(|(key, val)| {
+						self.resolve_intrinsics(Arc::new(val.clone()), visited)
+							.map(|arc| (Arc::clone(key), (*arc).clone()))
+					})
+					.collect::<Result<Vec<_>, _>>()?;
+				Ok(Arc::new(Value::Map(Arc::new(resolved))))
+			},
 			_ => Ok(value)
 		}
 	}
@@ -629,152 +1220,33 @@ impl Runtime {
 		items: &[Value],
 		visited: &mut FnvHashSet<(Arc<String>, Arc<String>)>
 	) -> Result<Vec<Arc<Value>>, RuntimeError> {
-		let args = items.iter().skip(1);
-		let arg_count = args.clone().count();
-
-		if arg_count == 0 {
-			return Err(RuntimeError::InvalidIntrinsicArgs);
-		}
-
-		if name.starts_with("std") {
-			let expected_count = match name {
-				// Single argument functions
-				"std_to_uppercase" | "std_to_lowercase" | "std_base64_encode" |
-				"std_base64_decode" | "std_float_to_int" | "std_int_to_float" |
-				"std_bitwise_not" => 1,
-
-				// Two argument functions
-				"std_add_int" | "std_sub_int" | "std_mul_int" | "std_div_int" |
-				"std_mod_int" | "std_add_float" | "std_sub_float" | "std_mul_float" |
-				"std_div_float" | "std_try" | "std_lt" | "std_gt" | "std_eq" |
-				"std_bitwise_and" | "std_bitwise_or" | "std_bitwise_xor" => 2,
-
-				// Three argument functions
-				"std_substring" | "std_if" | "std_replace" | "std_concat" => 3,
-
-				// Special cases
-				"std_hash" => 2,
-
-				_ => return Err(RuntimeError::UnknownIntrinsic(name.to_string())),
-			};
-
-			if arg_count != expected_count {
-				return Err(RuntimeError::InvalidIntrinsicArgs);
-			}
-		}
-
+		// Resolve every argument first, then drive validation from the function's
+		// registered signature. Arity and per-argument kind checks are no longer
+		// hardcoded here: each intrinsic carries its own descriptor.
 		let resolved_args = items.iter()
 			.skip(1)
 			.map(|item| self.resolve_value(Arc::new(item.clone()), visited))
 			.collect::<Result<Vec<_>, _>>()?;
 
-		// Validate argument types
-		for (idx, arg) in resolved_args.iter().enumerate() {
-			match (name, idx) {
-				// Integer operations
-				("std_add_int" | "std_sub_int" | "std_mul_int" | "std_div_int" |
-				"std_mod_int" | "std_bitwise_and" | "std_bitwise_or" |
-				"std_bitwise_xor", _) => {
-					if !matches!(&**arg, Value::Number(Number::Integer(_))) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							format!("{} requires integer arguments", name)
-						));
-					}
-				}
-
-				// Float operations
-				("std_add_float" | "std_sub_float" | "std_mul_float" |
-				"std_div_float", _) => {
-					if !matches!(&**arg, Value::Number(Number::Float(_))) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							format!("{} requires float arguments", name)
-						));
-					}
-				}
-
-				// String operations
-				("std_to_uppercase" | "std_to_lowercase" | "std_base64_encode" |
-				"std_base64_decode", 0) => {
-					if !matches!(&**arg, Value::String(_)) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							format!("{} requires string argument", name)
-						));
-					}
-				}
-
-				// Substring operation
-				("std_substring", 0) | ("std_replace", 0) => {
-					if !matches!(&**arg, Value::String(_)) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							format!("{} first argument must be string", name)
-						));
-					}
-				}
-				("std_substring", 1..=2) => {
-					if !matches!(&**arg, Value::Number(Number::Integer(_))) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							"std_substring indices must be integers".to_string()
-						));
-					}
-				}
-
-				// Replace operation
-				("std_replace", 1..=2) => {
-					if !matches!(&**arg, Value::String(_)) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							"std_replace requires string arguments".to_string()
-						));
-					}
-				}
-
-				// Conversion operations
-				("std_int_to_float", 0) => {
-					if !matches!(&**arg, Value::Number(Number::Integer(_))) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							"std_int_to_float requires integer argument".to_string()
-						));
-					}
-				}
-				("std_float_to_int", 0) => {
-					if !matches!(&**arg, Value::Number(Number::Float(_))) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							"std_float_to_int requires float argument".to_string()
-						));
-					}
-				}
-
-				// Control flow
-				("std_if", 0) => {
-					if !matches!(&**arg, Value::Boolean(_)) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							"std_if first argument must be boolean".to_string()
-						));
-					}
-				}
-
-				// Hash operation
-				("std_hash", 0) => {
-					if !matches!(&**arg, Value::String(_)) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							"std_hash first argument must be string".to_string()
-						));
-					}
-				}
-				("std_hash", 1) => {
-					if !matches!(&**arg, Value::String(_)) {
-						return Err(RuntimeError::IntrinsicTypeMismatch(
-							"std_hash second argument must be string".to_string()
-						));
-					}
-				}
-
-				_ => {}
-			}
+		match self.std_lib_loader.get_signature(name) {
+			Some(signature) => signature.validate(name, &resolved_args)?,
+			None => return Err(RuntimeError::UnknownIntrinsic(name.to_string())),
 		}
 
 		Ok(resolved_args)
 	}
 
+	/// Logs an intrinsic evaluation under [`Self::trace`]: the function name and
+	/// the type of each already-resolved argument.
+	fn trace_intrinsic(&self, name: &str, args: &[Arc<Value>]) {
+		let kinds = args
+			.iter()
+			.map(|a| value_type_name(a))
+			.collect::<Vec<_>>()
+			.join(", ");
+		eprintln!("[vtc trace] intrinsic {}!!({})", name, kinds);
+	}
+
 	fn collect_intrinsic_args(
 		&self,
 		items: &[Value],
@@ -785,4 +1257,96 @@ impl Runtime {
 			.map(|item| self.resolve_value(Arc::new(item.clone()), visited))
 			.collect()
 	}
+}
+
+/// Recursively gathers every reference embedded in a value, descending into
+/// lists and computed expressions so that inherited references nested inside a
+/// structure are validated too.
+fn collect_references<'a>(value: &'a Value, out: &mut Vec<&'a Reference>) {
+	match value {
+		Value::Reference(r) => out.push(r),
+		Value::List(items) => {
+			for item in items.iter() {
+				collect_references(item, out);
+			}
+		}
+		Value::Map(entries) => {
+			for (_, entry) in entries.iter() {
+				collect_references(entry, out);
+			}
+		}
+		Value::Expr(expr) => match &**expr {
+			Expr::Unary { operand, .. } => collect_references(operand, out),
+			Expr::Binary { lhs, rhs, .. } => {
+				collect_references(lhs, out);
+				collect_references(rhs, out);
+			}
+		},
+		_ => {}
+	}
+}
+
+/// Renders an ordered resolution path as `ns.var -> ns.var -> ...` for trace
+/// output and cycle reporting.
+fn format_trace_path(path: &[(Arc<String>, Arc<String>)]) -> String {
+	path.iter()
+		.map(|(ns, var)| format!("{}.{}", ns, var))
+		.collect::<Vec<_>>()
+		.join(" -> ")
+}
+
+/// Returns the parent of a dotted namespace path, dropping the final segment
+/// (`app.db.pool` → `app.db`), or `None` once a root-level name (no `.`) is
+/// reached.
+fn parent_namespace(namespace: &Arc<String>) -> Option<Arc<String>> {
+	namespace
+		.rfind('.')
+		.map(|idx| Arc::new(namespace[..idx].to_string()))
+}
+
+/// A short, stable name for a value's type, used when reporting a type-changing
+/// override during validation.
+fn value_type_name(value: &Value) -> &'static str {
+	match value {
+		Value::Intrinsic(_) => "intrinsic",
+		Value::String(_) => "string",
+		Value::Number(_) => "number",
+		Value::Boolean(_) => "boolean",
+		Value::Nil => "nil",
+		Value::List(_) => "list",
+		Value::Map(_) => "map",
+		Value::Reference(_) => "reference",
+		Value::Expr(_) => "expression",
+	}
+}
+
+/// Extracts a `Number` from a resolved value, erroring on non-numeric operands.
+fn as_number(value: &Arc<Value>) -> Result<Number, RuntimeError> {
+	match &**value {
+		Value::Number(n) => Ok(n.clone()),
+		_ => Err(RuntimeError::TypeError(
+			"expression operand is not a number".to_string(),
+		)),
+	}
+}
+
+/// Interprets any integer-flavoured `Number` as an `i64` (floats are truncated,
+/// out-of-range big integers saturate).
+fn number_as_i64(n: &Number) -> i64 {
+	match n {
+		Number::Integer(i) | Number::Binary(i) | Number::Hexadecimal(i) => *i,
+		Number::Float(f) => *f as i64,
+		Number::BigInt(b) => b
+			.to_i64()
+			.unwrap_or(if b.is_negative() { i64::MIN } else { i64::MAX }),
+	}
+}
+
+/// Widens any `Number` to `f64` for float-mode arithmetic and comparison.
+fn number_as_f64(n: &Number) -> f64 {
+	match n {
+		Number::Integer(i) | Number::Binary(i) | Number::Hexadecimal(i) => *i as f64,
+		Number::Float(f) => *f,
+		Number::BigInt(b) => b.to_f64(),
+	}
 }
\ No newline at end of file
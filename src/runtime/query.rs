@@ -0,0 +1,551 @@
+//! A small path query language over a [`Runtime`].
+//!
+//! [`Reference`]/[`Accessor`](crate::value::Accessor) already express single
+//! navigations (`->(i)`, `->(start, end)`, `->[key]`); this module, inspired by
+//! preserves-path, adds queries that select *many* values at once and filter by
+//! predicate. A query is a namespace/variable root followed by selection steps:
+//!
+//! ```text
+//! @app.$servers->*->[host]
+//! @app.$matrix->**[> 10]
+//! @app.$settings->[timeout]
+//! ```
+//!
+//! Supported steps: the existing index/range/key accessors (`->(i)`,
+//! `->(a, b)`, `->[key]`), a wildcard `->*` that descends into every element of
+//! a list (or every value of a map), a recursive-descent `->**` that visits
+//! every nested value, and a trailing predicate filter in brackets —
+//! `[type=integer]`, `[> 10]`, `[key=~"re"]`.
+//!
+//! Evaluation is a worklist over `Arc<Value>`: it starts from the root value
+//! set and maps it through each step, transparently resolving any
+//! [`Value::Reference`] (with the cycle detection already built into reference
+//! resolution) so queries cross namespace boundaries.
+
+use std::sync::Arc;
+
+use crate::runtime::error::RuntimeError;
+use crate::runtime::Runtime;
+use crate::value::{Number, Value};
+
+/// One selection step in a parsed query path.
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+	Index(usize),
+	Range(usize, usize),
+	Key(String),
+	/// `*` — every element of a list / value of a map.
+	Wildcard,
+	/// `**` — this value and every value nested within it.
+	RecursiveWildcard,
+	/// A trailing `[...]` predicate filter.
+	Predicate(Predicate),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+	/// `[type=integer]` — keep values of the named kind.
+	Type(String),
+	/// `[> 10]` etc. — keep numbers satisfying the comparison.
+	Compare(CmpOp, f64),
+	/// `[key=~"re"]` — keep strings containing the given substring.
+	Match(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+	Gt,
+	Lt,
+	Ge,
+	Le,
+	Eq,
+}
+
+/// A parsed query: a `(namespace, variable)` root plus a list of steps.
+#[derive(Debug, Clone, PartialEq)]
+struct Query {
+	namespace: String,
+	variable: String,
+	steps: Vec<Step>,
+}
+
+impl Runtime {
+	/// Runs `path` and returns the flattened set of matched values.
+	///
+	/// References encountered while descending are resolved through the normal
+	/// reference machinery, so a query transparently crosses namespaces.
+	///
+	/// # Errors
+	///
+	/// Returns [`RuntimeError::ParseError`] for a malformed path, and any error
+	/// raised while resolving the root or a crossed reference.
+	pub fn select(&self, path: &str) -> Result<Vec<Arc<Value>>, RuntimeError> {
+		let query = parse_query(path)?;
+		let root = self.get_value(&query.namespace, &query.variable, &[])?;
+
+		let mut current = vec![root];
+		for step in &query.steps {
+			let mut next = Vec::new();
+			for value in current {
+				self.apply_step(value, step, &mut next)?;
+			}
+			current = next;
+		}
+		Ok(current)
+	}
+
+	/// Rewrites every value matched by `path`, returning how many nodes were
+	/// changed. Useful for bulk config edits.
+	///
+	/// The rewrite walks the matched root variable's stored value tree, so it
+	/// does not follow references across namespaces (unlike [`select`]); a path
+	/// using `**` is rejected, since recursive in-place rewriting has no single
+	/// well-defined target.
+	///
+	/// [`select`]: Runtime::select
+	pub fn update<F>(&mut self, path: &str, f: F) -> Result<usize, RuntimeError>
+	where
+		F: Fn(&Value) -> Value,
+	{
+		let query = parse_query(path)?;
+		if query.steps.iter().any(|s| matches!(s, Step::RecursiveWildcard)) {
+			return Err(RuntimeError::ParseError(
+				"`**` is not supported in update paths".to_string(),
+			));
+		}
+
+		let root = self
+			.namespaces
+			.get(&Arc::new(query.namespace.clone()))
+			.ok_or_else(|| RuntimeError::NamespaceNotFound(query.namespace.clone()))?
+			.get(&Arc::new(query.variable.clone()))
+			.cloned()
+			.ok_or_else(|| RuntimeError::VariableNotFound(query.variable.clone()))?;
+		let mut count = 0;
+		let rewritten = rewrite(&root, &query.steps, &f, &mut count);
+		if count > 0 {
+			self.update_value(&query.namespace, &query.variable, (*rewritten).clone())?;
+		}
+		Ok(count)
+	}
+
+	/// Maps a single value through one step, pushing any results onto `out`.
+	fn apply_step(
+		&self,
+		value: Arc<Value>,
+		step: &Step,
+		out: &mut Vec<Arc<Value>>,
+	) -> Result<(), RuntimeError> {
+		let value = self.resolve_if_reference(value)?;
+
+		match step {
+			Step::Index(i) => {
+				if let Value::List(items) = &*value {
+					if let Some(item) = items.get(*i) {
+						out.push(Arc::new(item.clone()));
+					}
+				}
+			}
+			Step::Range(start, end) => {
+				if let Value::List(items) = &*value {
+					if *start <= *end && *end <= items.len() {
+						for item in &items[*start..*end] {
+							out.push(Arc::new(item.clone()));
+						}
+					}
+				}
+			}
+			Step::Key(key) => {
+				if let Some(found) = lookup_key(&value, key) {
+					out.push(found);
+				}
+			}
+			Step::Wildcard => match &*value {
+				Value::List(items) => {
+					for item in items.iter() {
+						out.push(Arc::new(item.clone()));
+					}
+				}
+				Value::Map(entries) => {
+					for (_, val) in entries.iter() {
+						out.push(Arc::new(val.clone()));
+					}
+				}
+				_ => {}
+			},
+			Step::RecursiveWildcard => collect_descendants(&value, out),
+			Step::Predicate(predicate) => {
+				if predicate.matches(&value) {
+					out.push(value);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Fully resolves `value` when it is a reference, leaving every other value
+	/// untouched.
+	fn resolve_if_reference(&self, value: Arc<Value>) -> Result<Arc<Value>, RuntimeError> {
+		match &*value {
+			Value::Reference(reference) => self.resolve_reference(reference),
+			_ => Ok(value),
+		}
+	}
+}
+
+/// Looks `key` up in a map or a dict-shaped list (`[k1, v1, k2, v2, ...]`).
+fn lookup_key(value: &Value, key: &str) -> Option<Arc<Value>> {
+	match value {
+		Value::Map(entries) => entries
+			.iter()
+			.find(|(k, _)| k.as_str() == key)
+			.map(|(_, v)| Arc::new(v.clone())),
+		Value::List(items) => items
+			.chunks(2)
+			.filter(|chunk| chunk.len() == 2)
+			.find(|chunk| matches!(&chunk[0], Value::String(k) if k == key))
+			.map(|chunk| Arc::new(chunk[1].clone())),
+		_ => None,
+	}
+}
+
+/// Pushes `value` and every value nested within it, reusing the list recursion
+/// `flatten_list` relies on and descending into maps as well.
+fn collect_descendants(value: &Arc<Value>, out: &mut Vec<Arc<Value>>) {
+	out.push(Arc::clone(value));
+	match &**value {
+		Value::List(items) => {
+			for item in items.iter() {
+				collect_descendants(&Arc::new(item.clone()), out);
+			}
+		}
+		Value::Map(entries) => {
+			for (_, val) in entries.iter() {
+				collect_descendants(&Arc::new(val.clone()), out);
+			}
+		}
+		_ => {}
+	}
+}
+
+impl Predicate {
+	fn matches(&self, value: &Value) -> bool {
+		match self {
+			Predicate::Type(kind) => query_type_name(value) == kind,
+			Predicate::Compare(op, rhs) => match number_as_f64(value) {
+				Some(lhs) => op.apply(lhs, *rhs),
+				None => false,
+			},
+			Predicate::Match(needle) => match value {
+				Value::String(s) => s.contains(needle.as_str()),
+				_ => false,
+			},
+		}
+	}
+}
+
+impl CmpOp {
+	fn apply(self, lhs: f64, rhs: f64) -> bool {
+		match self {
+			CmpOp::Gt => lhs > rhs,
+			CmpOp::Lt => lhs < rhs,
+			CmpOp::Ge => lhs >= rhs,
+			CmpOp::Le => lhs <= rhs,
+			CmpOp::Eq => lhs == rhs,
+		}
+	}
+}
+
+/// Rewrites the value tree along `steps`, applying `f` to every node the path
+/// selects. `**` is rejected by the caller, so it is treated as a no-op here.
+fn rewrite<F>(value: &Arc<Value>, steps: &[Step], f: &F, count: &mut usize) -> Arc<Value>
+where
+	F: Fn(&Value) -> Value,
+{
+	let (step, rest) = match steps.split_first() {
+		Some(split) => split,
+		None => {
+			*count += 1;
+			return Arc::new(f(value));
+		}
+	};
+
+	match step {
+		Step::Index(i) => match &**value {
+			Value::List(items) if *i < items.len() => {
+				let mut items = (**items).clone();
+				items[*i] = (*rewrite(&Arc::new(items[*i].clone()), rest, f, count)).clone();
+				Arc::new(Value::List(Arc::new(items)))
+			}
+			_ => Arc::clone(value),
+		},
+		Step::Range(start, end) => match &**value {
+			Value::List(items) if *start <= *end && *end <= items.len() => {
+				let mut items = (**items).clone();
+				for idx in *start..*end {
+					items[idx] = (*rewrite(&Arc::new(items[idx].clone()), rest, f, count)).clone();
+				}
+				Arc::new(Value::List(Arc::new(items)))
+			}
+			_ => Arc::clone(value),
+		},
+		Step::Key(key) => match &**value {
+			Value::Map(entries) => {
+				let mut entries = (**entries).clone();
+				for entry in entries.iter_mut() {
+					if entry.0.as_str() == key {
+						entry.1 = (*rewrite(&Arc::new(entry.1.clone()), rest, f, count)).clone();
+					}
+				}
+				Arc::new(Value::Map(Arc::new(entries)))
+			}
+			Value::List(items) => {
+				let mut items = (**items).clone();
+				let mut idx = 0;
+				while idx + 1 < items.len() {
+					if matches!(&items[idx], Value::String(k) if k == key) {
+						items[idx + 1] =
+							(*rewrite(&Arc::new(items[idx + 1].clone()), rest, f, count)).clone();
+					}
+					idx += 2;
+				}
+				Arc::new(Value::List(Arc::new(items)))
+			}
+			_ => Arc::clone(value),
+		},
+		Step::Wildcard => match &**value {
+			Value::List(items) => {
+				let rewritten = items
+					.iter()
+					.map(|item| (*rewrite(&Arc::new(item.clone()), rest, f, count)).clone())
+					.collect();
+				Arc::new(Value::List(Arc::new(rewritten)))
+			}
+			Value::Map(entries) => {
+				let rewritten = entries
+					.iter()
+					.map(|(k, v)| {
+						(Arc::clone(k), (*rewrite(&Arc::new(v.clone()), rest, f, count)).clone())
+					})
+					.collect();
+				Arc::new(Value::Map(Arc::new(rewritten)))
+			}
+			_ => Arc::clone(value),
+		},
+		Step::Predicate(predicate) => {
+			if predicate.matches(value) {
+				rewrite(value, rest, f, count)
+			} else {
+				Arc::clone(value)
+			}
+		}
+		Step::RecursiveWildcard => Arc::clone(value),
+	}
+}
+
+/// A finer type name than the serializer's: integers and floats are
+/// distinguished so `[type=integer]` and `[type=float]` both work.
+fn query_type_name(value: &Value) -> &'static str {
+	match value {
+		Value::Intrinsic(_) => "intrinsic",
+		Value::String(_) => "string",
+		Value::Number(Number::Float(_)) => "float",
+		Value::Number(_) => "integer",
+		Value::Boolean(_) => "boolean",
+		Value::Nil => "nil",
+		Value::List(_) => "list",
+		Value::Map(_) => "map",
+		Value::Reference(_) => "reference",
+		Value::Expr(_) => "expression",
+	}
+}
+
+/// Widens any numeric value to `f64` for predicate comparison; `None` for
+/// non-numbers.
+fn number_as_f64(value: &Value) -> Option<f64> {
+	match value {
+		Value::Number(Number::Integer(i))
+		| Value::Number(Number::Binary(i))
+		| Value::Number(Number::Hexadecimal(i)) => Some(*i as f64),
+		Value::Number(Number::Float(f)) => Some(*f),
+		Value::Number(Number::BigInt(b)) => Some(b.to_f64()),
+		_ => None,
+	}
+}
+
+// --- parsing -----------------------------------------------------------------
+
+fn parse_query(path: &str) -> Result<Query, RuntimeError> {
+	let mut parser = PathParser::new(path);
+	parser.parse()
+}
+
+struct PathParser<'a> {
+	chars: Vec<char>,
+	pos: usize,
+	src: &'a str,
+}
+
+impl<'a> PathParser<'a> {
+	fn new(src: &'a str) -> Self {
+		PathParser {
+			chars: src.chars().collect(),
+			pos: 0,
+			src,
+		}
+	}
+
+	fn err(&self, msg: impl Into<String>) -> RuntimeError {
+		RuntimeError::ParseError(format!("{} (in query `{}`)", msg.into(), self.src))
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.pos).copied()
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let c = self.peek();
+		if c.is_some() {
+			self.pos += 1;
+		}
+		c
+	}
+
+	fn eat(&mut self, expected: char) -> Result<(), RuntimeError> {
+		match self.bump() {
+			Some(c) if c == expected => Ok(()),
+			_ => Err(self.err(format!("expected `{}`", expected))),
+		}
+	}
+
+	fn take_while(&mut self, pred: impl Fn(char) -> bool) -> String {
+		let mut out = String::new();
+		while let Some(c) = self.peek() {
+			if pred(c) {
+				out.push(c);
+				self.pos += 1;
+			} else {
+				break;
+			}
+		}
+		out
+	}
+
+	fn parse(&mut self) -> Result<Query, RuntimeError> {
+		self.eat('@')?;
+		let namespace = self.parse_namespace()?;
+		self.eat('$')?;
+		let variable = self.take_while(|c| c != '-' && c != '[');
+		if variable.is_empty() {
+			return Err(self.err("missing variable after `$`"));
+		}
+
+		let mut steps = Vec::new();
+		while let Some(c) = self.peek() {
+			if c == '-' {
+				self.eat('-')?;
+				self.eat('>')?;
+				steps.push(self.parse_accessor_step()?);
+			} else if c == '[' {
+				steps.push(Step::Predicate(self.parse_predicate()?));
+			} else {
+				return Err(self.err(format!("unexpected character `{}`", c)));
+			}
+		}
+		Ok(Query {
+			namespace,
+			variable,
+			steps,
+		})
+	}
+
+	/// Parses the (possibly dotted) namespace path up to the `.` that
+	/// introduces the `$variable` segment, so `app.db.$pool` yields the
+	/// namespace `app.db` rather than stopping at the first dot.
+	fn parse_namespace(&mut self) -> Result<String, RuntimeError> {
+		let raw = self.take_while(|c| c != '$');
+		let namespace = raw
+			.strip_suffix('.')
+			.ok_or_else(|| self.err("missing `.` before `$variable`"))?;
+		if namespace.is_empty() {
+			return Err(self.err("missing namespace after `@`"));
+		}
+		Ok(namespace.to_string())
+	}
+
+	fn parse_accessor_step(&mut self) -> Result<Step, RuntimeError> {
+		match self.peek() {
+			Some('(') => self.parse_paren_accessor(),
+			Some('[') => {
+				self.eat('[')?;
+				let key = self.take_while(|c| c != ']');
+				self.eat(']')?;
+				Ok(Step::Key(key.trim().to_string()))
+			}
+			Some('*') => {
+				self.eat('*')?;
+				if self.peek() == Some('*') {
+					self.eat('*')?;
+					Ok(Step::RecursiveWildcard)
+				} else {
+					Ok(Step::Wildcard)
+				}
+			}
+			_ => Err(self.err("expected accessor after `->`")),
+		}
+	}
+
+	fn parse_paren_accessor(&mut self) -> Result<Step, RuntimeError> {
+		self.eat('(')?;
+		let body = self.take_while(|c| c != ')');
+		self.eat(')')?;
+		let body = body.trim();
+		if let Some((start, end)) = body.split_once(',') {
+			let start = parse_usize(start.trim()).ok_or_else(|| self.err("invalid range start"))?;
+			let end = parse_usize(end.trim()).ok_or_else(|| self.err("invalid range end"))?;
+			Ok(Step::Range(start, end))
+		} else {
+			let index = parse_usize(body).ok_or_else(|| self.err("invalid index"))?;
+			Ok(Step::Index(index))
+		}
+	}
+
+	fn parse_predicate(&mut self) -> Result<Predicate, RuntimeError> {
+		self.eat('[')?;
+		let body = self.take_while(|c| c != ']');
+		self.eat(']')?;
+		let body = body.trim();
+
+		if let Some(kind) = body.strip_prefix("type=") {
+			return Ok(Predicate::Type(kind.trim().to_string()));
+		}
+		if let Some(rest) = body.strip_prefix("key=~") {
+			let needle = rest.trim().trim_matches('"').to_string();
+			return Ok(Predicate::Match(needle));
+		}
+
+		let (op, rest) = if let Some(rest) = body.strip_prefix(">=") {
+			(CmpOp::Ge, rest)
+		} else if let Some(rest) = body.strip_prefix("<=") {
+			(CmpOp::Le, rest)
+		} else if let Some(rest) = body.strip_prefix('>') {
+			(CmpOp::Gt, rest)
+		} else if let Some(rest) = body.strip_prefix('<') {
+			(CmpOp::Lt, rest)
+		} else if let Some(rest) = body.strip_prefix('=') {
+			(CmpOp::Eq, rest)
+		} else {
+			return Err(self.err(format!("unrecognised predicate `{}`", body)));
+		};
+
+		let number = rest
+			.trim()
+			.parse::<f64>()
+			.map_err(|_| self.err("predicate comparison needs a number"))?;
+		Ok(Predicate::Compare(op, number))
+	}
+}
+
+fn parse_usize(text: &str) -> Option<usize> {
+	text.parse::<usize>().ok()
+}
@@ -102,6 +102,37 @@ impl Runtime {
 		Ok(())
 	}
 
+	/// Renders the runtime's loaded state back to VTC text. Namespaces are
+	/// emitted in sorted order for stable output, reusing the same value
+	/// serializer as [`Runtime::dump_to_file`].
+	pub fn to_vtc_string(&self) -> String {
+		let mut buffer = String::with_capacity(INITIAL_BUFFER_SIZE);
+
+		let mut namespaces: Vec<_> = self.namespaces.iter().collect();
+		namespaces.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+		for (namespace, variables) in namespaces {
+			buffer.push('@');
+			buffer.push_str(namespace);
+			buffer.push_str(":\n");
+
+			let mut vars: Vec<_> = variables.iter().collect();
+			vars.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+
+			for (var_name, value) in vars {
+				buffer.push('\t');
+				buffer.push('$');
+				buffer.push_str(var_name);
+				buffer.push_str(" := ");
+				self.serialize_value_to_string(value, &mut buffer);
+				buffer.push('\n');
+			}
+			buffer.push('\n');
+		}
+
+		buffer
+	}
+
 	pub fn serialize_value(&self, value: &Value) -> String {
 		let mut buffer = String::with_capacity(64);
 		self.serialize_value_to_string(value, &mut buffer);
@@ -124,6 +155,7 @@ impl Runtime {
 				Number::Float(f) => buffer.push_str(&f.to_string()),
 				Number::Binary(b) => write!(buffer, "0b{:b}", b).unwrap(),
 				Number::Hexadecimal(h) => write!(buffer, "0x{:X}", h).unwrap(),
+				Number::BigInt(b) => write!(buffer, "{}", b).unwrap(),
 			},
 			Value::Boolean(b) => buffer.push_str(if *b { "True" } else { "False" }),
 			Value::List(list) => {
@@ -143,7 +175,22 @@ impl Runtime {
 				buffer.push_str(name);
 				buffer.push_str("!!");
 			},
+			Value::Map(map) => {
+				buffer.push('{');
+				for (i, (key, val)) in map.iter().enumerate() {
+					if i > 0 {
+						buffer.push_str(", ");
+					}
+					write!(buffer, "{}: ", key).unwrap();
+					self.serialize_value_to_string(val, buffer);
+				}
+				buffer.push('}');
+			},
 			Value::Nil => buffer.push_str("Nil"),
+			Value::Expr(_) => {
+				// Computed expressions serialize via their textual form.
+				write!(buffer, "{}", value).unwrap();
+			},
 		}
 	}
 
@@ -190,6 +237,11 @@ impl Runtime {
 				Accessor::Index(i) => write!(buffer, "->({})", i).unwrap(),
 				Accessor::Range(start, end) => write!(buffer, "->({}, {})", start, end).unwrap(),
 				Accessor::Key(key) => write!(buffer, "->[{}]", key).unwrap(),
+				Accessor::IndexFromEnd(n) => write!(buffer, "->(-{})", n).unwrap(),
+				Accessor::RangeFrom(start) => write!(buffer, "->({}..)", start).unwrap(),
+				Accessor::RangeTo(end) => write!(buffer, "->(..{})", end).unwrap(),
+				Accessor::RangeFull => write!(buffer, "->(..)").unwrap(),
+				Accessor::Optional(inner) => write!(buffer, "->?{}", inner).unwrap(),
 			}
 		}
 	}
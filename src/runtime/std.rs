@@ -5,28 +5,157 @@ use std::sync::Arc;
 use anyhow::{anyhow, Result};
 use base64::{alphabet, Engine as _, engine::{self, general_purpose}};
 use fnv::FnvHashMap;
-use sha2::{Digest, Sha256};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
 
+use crate::bignum::BigInt;
 use crate::value::Number;
 use crate::value::Value;
 
-pub type VtcFn = Box<dyn Fn(Vec<Arc<Value>>) -> Arc<Value> + Send + Sync>;
+pub type VtcFn = Box<dyn Fn(Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> + Send + Sync>;
+
+use crate::runtime::error::RuntimeError;
+
+/// The kind expected for a single intrinsic argument. `Any` accepts every value
+/// and is the default for custom functions registered without a richer
+/// signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+	Integer,
+	Float,
+	String,
+	Boolean,
+	List,
+	Any,
+}
+
+impl ArgKind {
+	/// Returns whether `value` satisfies this kind. `Integer` also accepts the
+	/// arbitrary-precision [`Number::BigInt`] variant.
+	pub fn matches(&self, value: &Value) -> bool {
+		match self {
+			ArgKind::Integer => matches!(
+				value,
+				Value::Number(Number::Integer(_)) | Value::Number(Number::BigInt(_))
+			),
+			ArgKind::Float => matches!(value, Value::Number(Number::Float(_))),
+			ArgKind::String => matches!(value, Value::String(_)),
+			ArgKind::Boolean => matches!(value, Value::Boolean(_)),
+			ArgKind::List => matches!(value, Value::List(_)),
+			ArgKind::Any => true,
+		}
+	}
+}
+
+impl fmt::Display for ArgKind {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let s = match self {
+			ArgKind::Integer => "integer",
+			ArgKind::Float => "float",
+			ArgKind::String => "string",
+			ArgKind::Boolean => "boolean",
+			ArgKind::List => "list",
+			ArgKind::Any => "any",
+		};
+		write!(f, "{}", s)
+	}
+}
+
+/// How many arguments an intrinsic accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+	/// Exactly `n` arguments.
+	Exact(usize),
+	/// At least `min`, and at most `max` when `Some`.
+	Variadic { min: usize, max: Option<usize> },
+}
+
+impl Arity {
+	pub fn accepts(&self, count: usize) -> bool {
+		match self {
+			Arity::Exact(n) => count == *n,
+			Arity::Variadic { min, max } => count >= *min && max.map_or(true, |m| count <= m),
+		}
+	}
+}
+
+/// A descriptor that drives generic argument validation for an intrinsic: an
+/// [`Arity`] plus the ordered [`ArgKind`] expected for each position. For a
+/// variadic function the final kind applies to every extra argument.
+#[derive(Debug, Clone)]
+pub struct Signature {
+	pub arity: Arity,
+	pub args: Vec<ArgKind>,
+}
+
+impl Signature {
+	/// A fixed-arity signature whose argument count is the length of `kinds`.
+	pub fn exact(kinds: &[ArgKind]) -> Self {
+		Signature { arity: Arity::Exact(kinds.len()), args: kinds.to_vec() }
+	}
+
+	/// A variadic signature; `kinds` describes the leading positions and its last
+	/// entry is reused for any further arguments.
+	pub fn variadic(min: usize, max: Option<usize>, kinds: &[ArgKind]) -> Self {
+		Signature { arity: Arity::Variadic { min, max }, args: kinds.to_vec() }
+	}
+
+	/// The fully permissive signature used when a custom function is registered
+	/// without one: any number of arguments of any kind.
+	pub fn permissive() -> Self {
+		Signature { arity: Arity::Variadic { min: 0, max: None }, args: vec![ArgKind::Any] }
+	}
+
+	/// The expected kind at position `idx`, reusing the last declared kind for
+	/// trailing variadic arguments.
+	fn kind_at(&self, idx: usize) -> ArgKind {
+		self.args.get(idx).copied().or_else(|| self.args.last().copied()).unwrap_or(ArgKind::Any)
+	}
+
+	/// Validates already-resolved `args` against this signature, producing the
+	/// same `InvalidIntrinsicArgs` / `IntrinsicTypeMismatch` errors the hardcoded
+	/// checks used to raise.
+	pub fn validate(&self, name: &str, args: &[Arc<Value>]) -> Result<(), RuntimeError> {
+		if !self.arity.accepts(args.len()) {
+			return Err(RuntimeError::InvalidIntrinsicArgs);
+		}
+		for (idx, arg) in args.iter().enumerate() {
+			let kind = self.kind_at(idx);
+			if !kind.matches(arg) {
+				return Err(RuntimeError::IntrinsicTypeMismatch(format!(
+					"{} argument {} must be {}",
+					name,
+					idx + 1,
+					kind,
+				)));
+			}
+		}
+		Ok(())
+	}
+}
+
+/// A registered intrinsic: its validation [`Signature`] paired with the callable.
+pub struct RegisteredIntrinsic {
+	pub signature: Signature,
+	pub func: VtcFn,
+}
 
 // Helper functions
 mod helpers {
 	use super::*;
 
-	pub fn extract_number(value: &Arc<Value>) -> Number {
+	pub fn extract_number(value: &Arc<Value>) -> Result<Number, RuntimeError> {
 		match &**value {
-			Value::Number(n) => n.clone(),
-			_ => panic!("Expected a Number value"),
+			Value::Number(n) => Ok(n.clone()),
+			_ => Err(RuntimeError::TypeError("Expected a Number value".to_string())),
 		}
 	}
 
-	pub fn extract_string(value: &Arc<Value>) -> String {
+	pub fn extract_string(value: &Arc<Value>) -> Result<String, RuntimeError> {
 		match &**value {
-			Value::String(s) => (**s).to_string(),
-			_ => panic!("Expected a String value"),
+			Value::String(s) => Ok((**s).to_string()),
+			_ => Err(RuntimeError::TypeError("Expected a String value".to_string())),
 		}
 	}
 }
@@ -36,153 +165,447 @@ mod arithmetic {
 	use super::*;
 
 	// Integer operations
-	pub fn std_add_int(i1: Number, i2: Number) -> Number {
-		match (i1, i2) {
-			(Number::Integer(val1), Number::Integer(val2)) => Number::Integer(val1.wrapping_add(val2)),
-			_ => panic!("Both inputs must be integers: {} and {}", i1, i2),
+	//
+	// Each of `add`/`sub`/`mul` tries the `i64` fast path first and promotes to
+	// `BigInt` on overflow, demoting the result back to `Number::Integer` when it
+	// once again fits in an `i64`.
+	fn int_pair(i1: &Number, i2: &Number) -> Result<(i64, i64), RuntimeError> {
+		match (i1.as_i64(), i2.as_i64()) {
+			(Some(a), Some(b)) => Ok((a, b)),
+			_ => Err(RuntimeError::IntrinsicTypeMismatch(format!(
+				"both inputs must be integers: {} and {}",
+				i1, i2
+			))),
+		}
+	}
+
+	/// Promotes both operands to [`BigInt`], erroring if either is a float.
+	/// Used by the overflow path and whenever an operand is already a big
+	/// integer that does not fit in an `i64`.
+	fn big_pair(i1: &Number, i2: &Number) -> Result<(BigInt, BigInt), RuntimeError> {
+		match (i1.to_bigint(), i2.to_bigint()) {
+			(Some(a), Some(b)) => Ok((a, b)),
+			_ => Err(RuntimeError::IntrinsicTypeMismatch(format!(
+				"both inputs must be integers: {} and {}",
+				i1, i2
+			))),
 		}
 	}
 
-	pub fn std_sub_int(i1: Number, i2: Number) -> Number {
-		match (i1, i2) {
-			(Number::Integer(val1), Number::Integer(val2)) => Number::Integer(val1.wrapping_sub(val2)),
-			_ => panic!("Both inputs must be integers: {} and {}", i1, i2),
+	pub fn std_add_int(i1: Number, i2: Number) -> Result<Number, RuntimeError> {
+		match (i1.as_i64(), i2.as_i64()) {
+			(Some(a), Some(b)) => Ok(match a.checked_add(b) {
+				Some(v) => Number::Integer(v),
+				None => Number::from_bigint(BigInt::from_i64(a).add(&BigInt::from_i64(b))),
+			}),
+			_ => {
+				let (a, b) = big_pair(&i1, &i2)?;
+				Ok(Number::from_bigint(a.add(&b)))
+			}
+		}
+	}
+
+	pub fn std_sub_int(i1: Number, i2: Number) -> Result<Number, RuntimeError> {
+		match (i1.as_i64(), i2.as_i64()) {
+			(Some(a), Some(b)) => Ok(match a.checked_sub(b) {
+				Some(v) => Number::Integer(v),
+				None => Number::from_bigint(BigInt::from_i64(a).sub(&BigInt::from_i64(b))),
+			}),
+			_ => {
+				let (a, b) = big_pair(&i1, &i2)?;
+				Ok(Number::from_bigint(a.sub(&b)))
+			}
 		}
 	}
 
-	pub fn std_mul_int(i1: Number, i2: Number) -> Number {
-		match (i1, i2) {
-			(Number::Integer(val1), Number::Integer(val2)) => Number::Integer(val1.wrapping_mul(val2)),
-			_ => panic!("Both inputs must be integers: {} and {}", i1, i2),
+	pub fn std_mul_int(i1: Number, i2: Number) -> Result<Number, RuntimeError> {
+		match (i1.as_i64(), i2.as_i64()) {
+			(Some(a), Some(b)) => Ok(match a.checked_mul(b) {
+				Some(v) => Number::Integer(v),
+				None => Number::from_bigint(BigInt::from_i64(a).mul(&BigInt::from_i64(b))),
+			}),
+			_ => {
+				let (a, b) = big_pair(&i1, &i2)?;
+				Ok(Number::from_bigint(a.mul(&b)))
+			}
 		}
 	}
 
-	pub fn std_div_int(i1: Number, i2: Number) -> Number {
-		match (i1, i2) {
-			(Number::Integer(val1), Number::Integer(val2)) if val2 != 0 => Number::Integer(val1 / val2),
-			(Number::Integer(_), Number::Integer(0)) => panic!("Division by zero"),
-			_ => panic!("Both inputs must be integers: {} and {}", i1, i2),
+	pub fn std_div_int(i1: Number, i2: Number) -> Result<Number, RuntimeError> {
+		// A BigInt operand is accepted only when it fits in an `i64`; a big
+		// integer outside that range is rejected like any non-integer. The one
+		// quotient that overflows, `i64::MIN / -1`, is promoted to BigInt.
+		if matches!(i2.as_i64(), Some(0)) {
+			return Err(RuntimeError::ConversionError("division by zero".to_string()));
 		}
+		let (val1, val2) = int_pair(&i1, &i2)?;
+		Ok(match val1.checked_div(val2) {
+			Some(v) => Number::Integer(v),
+			None => Number::from_bigint(BigInt::from_i64(val1).neg()),
+		})
 	}
 
-	pub fn std_mod_int(i1: Number, i2: Number) -> Number {
-		match (i1, i2) {
-			(Number::Integer(val1), Number::Integer(val2)) if val2 != 0 => Number::Integer(val1 % val2),
-			(Number::Integer(_), Number::Integer(0)) => panic!("Modulo by zero"),
-			_ => panic!("Both inputs must be integers: {} and {}", i1, i2),
+	pub fn std_mod_int(i1: Number, i2: Number) -> Result<Number, RuntimeError> {
+		if matches!(i2.as_i64(), Some(0)) {
+			return Err(RuntimeError::ConversionError("modulo by zero".to_string()));
 		}
+		let (val1, val2) = int_pair(&i1, &i2)?;
+		Ok(Number::Integer(val1 % val2))
 	}
 
 	// Float operations
-	pub fn std_add_float(f1: Number, f2: Number) -> Number {
-		match (f1, f2) {
-			(Number::Float(val1), Number::Float(val2)) => Number::Float(val1 + val2),
-			_ => panic!("Both inputs must be floats: {} and {}", f1, f2),
+	pub fn std_add_float(f1: Number, f2: Number) -> Result<Number, RuntimeError> {
+		match (&f1, &f2) {
+			(Number::Float(val1), Number::Float(val2)) => Ok(Number::Float(val1 + val2)),
+			_ => Err(both_floats(&f1, &f2)),
+		}
+	}
+
+	pub fn std_sub_float(f1: Number, f2: Number) -> Result<Number, RuntimeError> {
+		match (&f1, &f2) {
+			(Number::Float(val1), Number::Float(val2)) => Ok(Number::Float(val1 - val2)),
+			_ => Err(both_floats(&f1, &f2)),
+		}
+	}
+
+	pub fn std_mul_float(f1: Number, f2: Number) -> Result<Number, RuntimeError> {
+		match (&f1, &f2) {
+			(Number::Float(val1), Number::Float(val2)) => Ok(Number::Float(val1 * val2)),
+			_ => Err(both_floats(&f1, &f2)),
+		}
+	}
+
+	pub fn std_div_float(f1: Number, f2: Number) -> Result<Number, RuntimeError> {
+		match (&f1, &f2) {
+			(Number::Float(val1), Number::Float(val2)) if *val2 != 0.0 => Ok(Number::Float(val1 / val2)),
+			(Number::Float(_), Number::Float(val2)) if *val2 == 0.0 => {
+				Err(RuntimeError::ConversionError("division by zero".to_string()))
+			}
+			_ => Err(both_floats(&f1, &f2)),
+		}
+	}
+
+	fn both_floats(f1: &Number, f2: &Number) -> RuntimeError {
+		RuntimeError::IntrinsicTypeMismatch(format!("both inputs must be floats: {} and {}", f1, f2))
+	}
+
+	// Coercing operations
+	//
+	// The usual numeric-tower promotion: if both operands are integers the
+	// result stays integer (and still promotes to BigInt on overflow); if either
+	// operand is a float both are computed in `f64`. These back the type-agnostic
+	// `std_add`/`std_sub`/`std_mul`/`std_div` entry points, while the strict
+	// `*_int`/`*_float` variants remain for callers that want exact typing.
+	pub fn std_add(n1: Number, n2: Number) -> Result<Number, RuntimeError> {
+		if is_float(&n1) || is_float(&n2) {
+			Ok(Number::Float(as_float(&n1)? + as_float(&n2)?))
+		} else {
+			std_add_int(n1, n2)
+		}
+	}
+
+	pub fn std_sub(n1: Number, n2: Number) -> Result<Number, RuntimeError> {
+		if is_float(&n1) || is_float(&n2) {
+			Ok(Number::Float(as_float(&n1)? - as_float(&n2)?))
+		} else {
+			std_sub_int(n1, n2)
 		}
 	}
 
-	pub fn std_sub_float(f1: Number, f2: Number) -> Number {
-		match (f1, f2) {
-			(Number::Float(val1), Number::Float(val2)) => Number::Float(val1 - val2),
-			_ => panic!("Both inputs must be floats: {} and {}", f1, f2),
+	pub fn std_mul(n1: Number, n2: Number) -> Result<Number, RuntimeError> {
+		if is_float(&n1) || is_float(&n2) {
+			Ok(Number::Float(as_float(&n1)? * as_float(&n2)?))
+		} else {
+			std_mul_int(n1, n2)
 		}
 	}
 
-	pub fn std_mul_float(f1: Number, f2: Number) -> Number {
-		match (f1, f2) {
-			(Number::Float(val1), Number::Float(val2)) => Number::Float(val1 * val2),
-			_ => panic!("Both inputs must be floats: {} and {}", f1, f2),
+	pub fn std_div(n1: Number, n2: Number) -> Result<Number, RuntimeError> {
+		if is_float(&n1) || is_float(&n2) {
+			let d = as_float(&n2)?;
+			if d == 0.0 {
+				return Err(RuntimeError::ConversionError("division by zero".to_string()));
+			}
+			Ok(Number::Float(as_float(&n1)? / d))
+		} else {
+			std_div_int(n1, n2)
 		}
 	}
 
-	pub fn std_div_float(f1: Number, f2: Number) -> Number {
-		match (f1, f2) {
-			(Number::Float(val1), Number::Float(val2)) if val2 != 0.0 => Number::Float(val1 / val2),
-			(Number::Float(_), Number::Float(val2)) if val2 == 0.0 => panic!("Division by zero"),
-			_ => panic!("Both inputs must be floats: {} and {}", f1, f2),
+	fn is_float(n: &Number) -> bool {
+		matches!(n, Number::Float(_))
+	}
+
+	/// Promotes any numeric variant to `f64`, erroring only on a value that is
+	/// somehow not a number (unreachable for the `Number` type).
+	fn as_float(n: &Number) -> Result<f64, RuntimeError> {
+		match n {
+			Number::Float(f) => Ok(*f),
+			Number::Integer(i) | Number::Binary(i) | Number::Hexadecimal(i) => Ok(*i as f64),
+			Number::BigInt(b) => Ok(b.to_f64()),
 		}
 	}
 }
 
 // Conversion operations
 mod conversion {
-	use crate::value::Number;
+	use super::*;
 
-	pub fn std_int_to_float(i: Number) -> Number {
-		match i {
-			Number::Integer(val) => Number::Float(val as f64),
-			_ => panic!("Input must be an integer: {}", i),
+	pub fn std_int_to_float(i: Number) -> Result<Number, RuntimeError> {
+		match &i {
+			Number::Integer(val) | Number::Binary(val) | Number::Hexadecimal(val) => {
+				Ok(Number::Float(*val as f64))
+			}
+			Number::BigInt(b) => Ok(Number::Float(b.to_f64())),
+			_ => Err(RuntimeError::IntrinsicTypeMismatch(format!(
+				"input must be an integer: {}",
+				i
+			))),
 		}
 	}
 
-	pub fn std_float_to_int(f: Number) -> Number {
-		match f {
-			Number::Float(val) => Number::Integer(val as i64),
-			_ => panic!("Input must be a float: {:?}", f),
+	pub fn std_float_to_int(f: Number) -> Result<Number, RuntimeError> {
+		match &f {
+			Number::Float(val) => Ok(Number::Integer(*val as i64)),
+			_ => Err(RuntimeError::IntrinsicTypeMismatch(format!(
+				"input must be a float: {:?}",
+				f
+			))),
 		}
 	}
 }
 
 // Comparison operations
 mod comparison {
-	use crate::value::Number;
+	use std::cmp::Ordering;
 
-	pub fn std_eq(n1: Number, n2: Number) -> bool {
-		match (n1, n2) {
-			(Number::Integer(i1), Number::Integer(i2)) => i1 == i2,
-			(Number::Float(f1), Number::Float(f2)) => f1 == f2,
-			_ => false,
+	use super::*;
+
+	pub fn std_eq(n1: Number, n2: Number) -> Result<bool, RuntimeError> {
+		// A float operand promotes the pair to `f64` (so `NaN` never equals
+		// itself); otherwise this is `Number`'s equality, which already treats
+		// an in-range `BigInt` as equal to the matching `Integer`.
+		match (&n1, &n2) {
+			(Number::Float(_), _) | (_, Number::Float(_)) => Ok(as_f64(&n1) == as_f64(&n2)),
+			_ => Ok(n1 == n2),
 		}
 	}
 
-	pub fn std_lt(n1: Number, n2: Number) -> bool {
+	/// Three-way comparison with a fallible total order. Two integers compare
+	/// exactly, falling back to `BigInt::cmp` when either side overflows
+	/// `i64`; any float operand promotes the pair to `f64` and defers to
+	/// `partial_cmp`, which returns `None` exactly when a `NaN` is involved —
+	/// surfaced as a `TypeError` rather than a misleading boolean so configs
+	/// never branch on an unorderable comparison.
+	pub fn cmp(n1: &Number, n2: &Number) -> Result<Ordering, RuntimeError> {
 		match (n1, n2) {
-			(Number::Integer(i1), Number::Integer(i2)) => i1 < i2,
-			(Number::Float(f1), Number::Float(f2)) => f1 < f2,
-			_ => panic!("Cannot compare different types: {} and {}", n1, n2),
+			(Number::Float(_), _) | (_, Number::Float(_)) => as_f64(n1)
+				.partial_cmp(&as_f64(n2))
+				.ok_or_else(|| RuntimeError::TypeError("cannot order NaN".to_string())),
+			_ => match (n1.as_i64(), n2.as_i64()) {
+				(Some(i1), Some(i2)) => Ok(i1.cmp(&i2)),
+				_ => match (n1.to_bigint(), n2.to_bigint()) {
+					(Some(b1), Some(b2)) => Ok(b1.cmp(&b2)),
+					_ => Err(different_types(n1, n2)),
+				},
+			},
 		}
 	}
 
-	pub fn std_gt(n1: Number, n2: Number) -> bool {
-		match (n1, n2) {
-			(Number::Integer(i1), Number::Integer(i2)) => i1 > i2,
-			(Number::Float(f1), Number::Float(f2)) => f1 > f2,
-			_ => panic!("Cannot compare different types: {} and {}", n1, n2),
+	pub fn std_lt(n1: Number, n2: Number) -> Result<bool, RuntimeError> {
+		Ok(cmp(&n1, &n2)? == Ordering::Less)
+	}
+
+	pub fn std_gt(n1: Number, n2: Number) -> Result<bool, RuntimeError> {
+		Ok(cmp(&n1, &n2)? == Ordering::Greater)
+	}
+
+	/// Returns `-1`, `0`, or `1` as a `Number::Integer`, built on the same
+	/// fallible total order as `std_lt`/`std_gt`.
+	pub fn std_cmp(n1: Number, n2: Number) -> Result<Number, RuntimeError> {
+		Ok(Number::Integer(match cmp(&n1, &n2)? {
+			Ordering::Less => -1,
+			Ordering::Equal => 0,
+			Ordering::Greater => 1,
+		}))
+	}
+
+	fn as_f64(n: &Number) -> f64 {
+		match n {
+			Number::Float(f) => *f,
+			Number::Integer(i) | Number::Binary(i) | Number::Hexadecimal(i) => *i as f64,
+			Number::BigInt(b) => b.to_f64(),
 		}
 	}
+
+	fn different_types(n1: &Number, n2: &Number) -> RuntimeError {
+		RuntimeError::TypeError(format!("cannot compare different types: {} and {}", n1, n2))
+	}
 }
 
 // Bitwise operations
 mod bitwise {
-	use crate::value::Number;
+	use super::*;
 
-	pub fn std_bitwise_and(i1: Number, i2: Number) -> Number {
-		match (i1, i2) {
-			(Number::Integer(val1), Number::Integer(val2)) => Number::Integer(val1 & val2),
-			_ => panic!("Both inputs must be integers: {} and {}", i1, i2),
+	pub fn std_bitwise_and(i1: Number, i2: Number) -> Result<Number, RuntimeError> {
+		match (&i1, &i2) {
+			(Number::Integer(val1), Number::Integer(val2)) => Ok(Number::Integer(val1 & val2)),
+			_ => Err(both_ints(&i1, &i2)),
 		}
 	}
 
-	pub fn std_bitwise_or(i1: Number, i2: Number) -> Number {
-		match (i1, i2) {
-			(Number::Integer(val1), Number::Integer(val2)) => Number::Integer(val1 | val2),
-			_ => panic!("Both inputs must be integers: {} and {}", i1, i2),
+	pub fn std_bitwise_or(i1: Number, i2: Number) -> Result<Number, RuntimeError> {
+		match (&i1, &i2) {
+			(Number::Integer(val1), Number::Integer(val2)) => Ok(Number::Integer(val1 | val2)),
+			_ => Err(both_ints(&i1, &i2)),
 		}
 	}
 
-	pub fn std_bitwise_xor(i1: Number, i2: Number) -> Number {
-		match (i1, i2) {
-			(Number::Integer(val1), Number::Integer(val2)) => Number::Integer(val1 ^ val2),
-			_ => panic!("Both inputs must be integers: {} and {}", i1, i2),
+	pub fn std_bitwise_xor(i1: Number, i2: Number) -> Result<Number, RuntimeError> {
+		match (&i1, &i2) {
+			(Number::Integer(val1), Number::Integer(val2)) => Ok(Number::Integer(val1 ^ val2)),
+			_ => Err(both_ints(&i1, &i2)),
 		}
 	}
 
-	pub fn std_bitwise_not(i: Number) -> Number {
-		match i {
-			Number::Integer(val) => Number::Integer(!val),
-			_ => panic!("Input must be an integer: {}", i),
+	pub fn std_bitwise_not(i: Number) -> Result<Number, RuntimeError> {
+		match &i {
+			Number::Integer(val) => Ok(Number::Integer(!val)),
+			_ => Err(RuntimeError::IntrinsicTypeMismatch(format!(
+				"input must be an integer: {}",
+				i
+			))),
 		}
 	}
+
+	fn both_ints(i1: &Number, i2: &Number) -> RuntimeError {
+		RuntimeError::IntrinsicTypeMismatch(format!(
+			"both inputs must be integers: {} and {}",
+			i1, i2
+		))
+	}
+}
+
+// Math operations
+//
+// Everything operates in `f64` (integers promote via `as f64`), except `abs`
+// and `pow`, which preserve `Number::Integer` when their inputs are integral
+// and the exponent is non-negative. Domain errors — `sqrt` of a negative,
+// `ln`/`log` of a non-positive — surface as `ConversionError` rather than
+// producing a silent `NaN`.
+mod math {
+	use super::*;
+
+	fn to_f64(n: &Number) -> f64 {
+		match n {
+			Number::Float(f) => *f,
+			Number::Integer(i) | Number::Binary(i) | Number::Hexadecimal(i) => *i as f64,
+			Number::BigInt(b) => b.to_f64(),
+		}
+	}
+
+	pub fn std_sqrt(n: Number) -> Result<Number, RuntimeError> {
+		let x = to_f64(&n);
+		if x < 0.0 {
+			return Err(RuntimeError::ConversionError("sqrt of a negative number".to_string()));
+		}
+		Ok(Number::Float(x.sqrt()))
+	}
+
+	pub fn std_abs(n: Number) -> Result<Number, RuntimeError> {
+		Ok(match n {
+			Number::Float(f) => Number::Float(f.abs()),
+			Number::Integer(i) | Number::Binary(i) | Number::Hexadecimal(i) => match i.checked_abs() {
+				Some(v) => Number::Integer(v),
+				None => Number::from_bigint(BigInt::from_i64(i).abs()),
+			},
+			Number::BigInt(b) => Number::from_bigint(b.abs()),
+		})
+	}
+
+	/// Above this, even exponentiation by squaring produces a `BigInt` with
+	/// millions of limbs; reject it instead of letting the process hang or
+	/// exhaust memory on a config-supplied exponent.
+	const MAX_POW_EXPONENT: i64 = 1_000_000;
+
+	pub fn std_pow(base: Number, exp: Number) -> Result<Number, RuntimeError> {
+		// Integer base and non-negative integer exponent stay integer, promoting
+		// to BigInt on overflow; anything else computes in f64.
+		if let (Some(b), Some(e)) = (base.as_i64(), exp.as_i64()) {
+			if e >= 0 {
+				if e > MAX_POW_EXPONENT {
+					return Err(RuntimeError::ConversionError(format!(
+						"exponent {} exceeds the maximum supported exponent of {}",
+						e, MAX_POW_EXPONENT
+					)));
+				}
+				return Ok(Number::from_bigint(bigint_pow(&BigInt::from_i64(b), e as u64)));
+			}
+		}
+		Ok(Number::Float(to_f64(&base).powf(to_f64(&exp))))
+	}
+
+	/// Exponentiation by squaring: O(log exp) big-integer multiplications
+	/// instead of the naive O(exp) repeated-multiply loop.
+	fn bigint_pow(base: &BigInt, mut exp: u64) -> BigInt {
+		let mut result = BigInt::from_i64(1);
+		let mut base = base.clone();
+		while exp > 0 {
+			if exp & 1 == 1 {
+				result = result.mul(&base);
+			}
+			base = base.mul(&base);
+			exp >>= 1;
+		}
+		result
+	}
+
+	pub fn std_floor(n: Number) -> Result<Number, RuntimeError> {
+		Ok(Number::Float(to_f64(&n).floor()))
+	}
+
+	pub fn std_ceil(n: Number) -> Result<Number, RuntimeError> {
+		Ok(Number::Float(to_f64(&n).ceil()))
+	}
+
+	pub fn std_round(n: Number) -> Result<Number, RuntimeError> {
+		Ok(Number::Float(to_f64(&n).round()))
+	}
+
+	pub fn std_exp(n: Number) -> Result<Number, RuntimeError> {
+		Ok(Number::Float(to_f64(&n).exp()))
+	}
+
+	pub fn std_ln(n: Number) -> Result<Number, RuntimeError> {
+		let x = to_f64(&n);
+		if x <= 0.0 {
+			return Err(RuntimeError::ConversionError("ln of a non-positive number".to_string()));
+		}
+		Ok(Number::Float(x.ln()))
+	}
+
+	pub fn std_log(n: Number, base: Number) -> Result<Number, RuntimeError> {
+		let x = to_f64(&n);
+		let b = to_f64(&base);
+		if x <= 0.0 || b <= 0.0 {
+			return Err(RuntimeError::ConversionError(
+				"log of a non-positive number or base".to_string(),
+			));
+		}
+		Ok(Number::Float(x.log(b)))
+	}
+
+	pub fn std_sin(n: Number) -> Result<Number, RuntimeError> {
+		Ok(Number::Float(to_f64(&n).sin()))
+	}
+
+	pub fn std_cos(n: Number) -> Result<Number, RuntimeError> {
+		Ok(Number::Float(to_f64(&n).cos()))
+	}
+
+	pub fn std_tan(n: Number) -> Result<Number, RuntimeError> {
+		Ok(Number::Float(to_f64(&n).tan()))
+	}
 }
 
 // String operations
@@ -191,59 +614,61 @@ mod string_ops {
 
 	use super::*;
 
-	pub fn std_to_uppercase(args: Vec<Arc<Value>>) -> Arc<Value> {
+	pub fn std_to_uppercase(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 		if args.len() != 1 {
-			panic!("std_to_uppercase expects 1 argument");
+			return Err(RuntimeError::InvalidIntrinsicArgs);
 		}
-		let s = helpers::extract_string(&args[0]);
-		Arc::new(Value::String(s.to_uppercase()))
+		let s = helpers::extract_string(&args[0])?;
+		Ok(Arc::new(Value::String(s.to_uppercase())))
 	}
 
-	pub fn std_to_lowercase(args: Vec<Arc<Value>>) -> Arc<Value> {
+	pub fn std_to_lowercase(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 		if args.len() != 1 {
-			panic!("std_to_lowercase expects 1 argument");
+			return Err(RuntimeError::InvalidIntrinsicArgs);
 		}
-		let s = helpers::extract_string(&args[0]);
-		Arc::new(Value::String(s.to_lowercase()))
+		let s = helpers::extract_string(&args[0])?;
+		Ok(Arc::new(Value::String(s.to_lowercase())))
 	}
 
-	pub fn std_substring(args: Vec<Arc<Value>>) -> Arc<Value> {
+	pub fn std_substring(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 		if args.len() != 3 {
-			panic!("std_substring expects 3 arguments");
+			return Err(RuntimeError::InvalidIntrinsicArgs);
 		}
-		let s = helpers::extract_string(&args[0]);
-		let start = helpers::extract_number(&args[1]);
-		let end = helpers::extract_number(&args[2]);
+		let s = helpers::extract_string(&args[0])?;
+		let start = helpers::extract_number(&args[1])?;
+		let end = helpers::extract_number(&args[2])?;
 
 		if let (Number::Integer(start), Number::Integer(end)) = (start, end) {
 			let start = start as usize;
 			let end = end as usize;
 			if start > end || end > s.len() {
-				panic!("Invalid range for substring");
+				return Err(RuntimeError::InvalidRange(start, end));
 			}
-			Arc::new(Value::String(s[start..end].to_string()))
+			Ok(Arc::new(Value::String(s[start..end].to_string())))
 		} else {
-			panic!("Start and end indices must be integers");
+			Err(RuntimeError::IntrinsicTypeMismatch(
+				"start and end indices must be integers".to_string(),
+			))
 		}
 	}
 
 	/// Appends two or more strings together
-	pub fn std_concat(args: Vec<Arc<Value>>) -> Arc<Value> {
+	pub fn std_concat(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 		let mut result = String::new();
 		for arg in args {
-			result.push_str(&helpers::extract_string(&arg));
+			result.push_str(&helpers::extract_string(&arg)?);
 		}
-		Arc::new(Value::String(result))
+		Ok(Arc::new(Value::String(result)))
 	}
 
-	pub fn std_replace(args: Vec<Arc<Value>>) -> Arc<Value> {
+	pub fn std_replace(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 		if args.len() != 3 {
-			panic!("std_replace expects 3 arguments");
+			return Err(RuntimeError::InvalidIntrinsicArgs);
 		}
-		let s = helpers::extract_string(&args[0]);
-		let from = helpers::extract_string(&args[1]);
-		let to = helpers::extract_string(&args[2]);
-		Arc::new(Value::String(s.replace(&from, &to)))
+		let s = helpers::extract_string(&args[0])?;
+		let from = helpers::extract_string(&args[1])?;
+		let to = helpers::extract_string(&args[2])?;
+		Ok(Arc::new(Value::String(s.replace(&from, &to))))
 	}
 }
 
@@ -254,43 +679,122 @@ mod advanced_ops {
 	const CUSTOM_ENGINE: engine::GeneralPurpose = engine::GeneralPurpose::new(
 		&alphabet::URL_SAFE, general_purpose::NO_PAD);
 
-	pub fn std_base64_encode(args: Vec<Arc<Value>>) -> Arc<Value> {
-		if args.len() != 1 {
-			panic!("std_base64_encode expects 1 argument");
+	/// Resolves a base64 alphabet/padding selector to an engine. The default
+	/// (`"url"`, no padding) keeps backward compatibility with callers that omit
+	/// the argument.
+	fn engine_for(name: &str) -> Result<engine::GeneralPurpose, RuntimeError> {
+		let (alpha, pad) = match name {
+			"url" => (&alphabet::URL_SAFE, general_purpose::NO_PAD),
+			"url_pad" => (&alphabet::URL_SAFE, general_purpose::PAD),
+			"standard" => (&alphabet::STANDARD, general_purpose::NO_PAD),
+			"standard_pad" => (&alphabet::STANDARD, general_purpose::PAD),
+			_ => {
+				return Err(RuntimeError::ConversionError(format!(
+					"unknown base64 alphabet: {}",
+					name
+				)))
+			}
+		};
+		Ok(engine::GeneralPurpose::new(alpha, pad))
+	}
+
+	pub fn std_base64_encode(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
+		if args.is_empty() || args.len() > 2 {
+			return Err(RuntimeError::InvalidIntrinsicArgs);
 		}
-		let s = helpers::extract_string(&args[0]);
-		Arc::new(Value::String(CUSTOM_ENGINE.encode(s)))
+		let s = helpers::extract_string(&args[0])?;
+		let encoded = match args.get(1) {
+			Some(selector) => engine_for(&helpers::extract_string(selector)?)?.encode(s),
+			None => CUSTOM_ENGINE.encode(s),
+		};
+		Ok(Arc::new(Value::String(encoded)))
 	}
 
-	pub fn std_base64_decode(args: Vec<Arc<Value>>) -> Arc<Value> {
-		if args.len() != 1 {
-			panic!("std_base64_decode expects 1 argument");
+	pub fn std_base64_decode(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
+		if args.is_empty() || args.len() > 2 {
+			return Err(RuntimeError::InvalidIntrinsicArgs);
 		}
-		let s = helpers::extract_string(&args[0]);
-		match CUSTOM_ENGINE.decode(s) {
+		let s = helpers::extract_string(&args[0])?;
+		let decoded = match args.get(1) {
+			Some(selector) => engine_for(&helpers::extract_string(selector)?)?.decode(s),
+			None => CUSTOM_ENGINE.decode(s),
+		};
+		match decoded {
 			Ok(decoded) => match String::from_utf8(decoded) {
-				Ok(decoded_str) => Arc::new(Value::String(decoded_str)),
-				Err(_) => panic!("Failed to convert decoded bytes to UTF-8 string"),
+				Ok(decoded_str) => Ok(Arc::new(Value::String(decoded_str))),
+				Err(_) => Err(RuntimeError::ConversionError(
+					"failed to convert decoded bytes to UTF-8 string".to_string(),
+				)),
 			},
-			Err(_) => panic!("Failed to decode base64 string"),
+			Err(_) => Err(RuntimeError::ConversionError(
+				"failed to decode base64 string".to_string(),
+			)),
 		}
 	}
 
-	pub fn std_hash(args: Vec<Arc<Value>>) -> Arc<Value> {
+	/// Computes a hex digest of `data` with the named algorithm.
+	fn digest_hex(algorithm: &str, data: &[u8]) -> Result<String, RuntimeError> {
+		let hex = match algorithm {
+			"sha256" => format!("{:x}", Sha256::digest(data)),
+			"sha384" => format!("{:x}", Sha384::digest(data)),
+			"sha512" => format!("{:x}", Sha512::digest(data)),
+			"sha1" => format!("{:x}", Sha1::digest(data)),
+			_ => {
+				return Err(RuntimeError::ConversionError(format!(
+					"unsupported hash algorithm: {}",
+					algorithm
+				)))
+			}
+		};
+		Ok(hex)
+	}
+
+	/// Computes a keyed HMAC hex digest with the named algorithm. An HMAC accepts
+	/// a key of any length, so key construction never fails.
+	fn hmac_hex(algorithm: &str, key: &[u8], data: &[u8]) -> Result<String, RuntimeError> {
+		macro_rules! run {
+			($hash:ty) => {{
+				let mut mac = Hmac::<$hash>::new_from_slice(key)
+					.expect("HMAC accepts a key of any length");
+				mac.update(data);
+				format!("{:x}", mac.finalize().into_bytes())
+			}};
+		}
+
+		let hex = match algorithm {
+			"sha256" => run!(Sha256),
+			"sha384" => run!(Sha384),
+			"sha512" => run!(Sha512),
+			"sha1" => run!(Sha1),
+			_ => {
+				return Err(RuntimeError::ConversionError(format!(
+					"unsupported hash algorithm: {}",
+					algorithm
+				)))
+			}
+		};
+		Ok(hex)
+	}
+
+	pub fn std_hash(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 		if args.len() != 2 {
-			panic!("std_hash expects 2 arguments");
-		}
-		let s = helpers::extract_string(&args[0]);
-		let algorithm = helpers::extract_string(&args[1]);
-		match algorithm.as_str() {
-			"sha256" => {
-				let mut hasher = Sha256::new();
-				hasher.update(s.as_bytes());
-				let result = hasher.finalize();
-				Arc::new(Value::String(format!("{:x}", result)))
-			},
-			_ => panic!("Unsupported hash algorithm: {}", algorithm),
+			return Err(RuntimeError::InvalidIntrinsicArgs);
+		}
+		let s = helpers::extract_string(&args[0])?;
+		let algorithm = helpers::extract_string(&args[1])?;
+		let hex = digest_hex(&algorithm, s.as_bytes())?;
+		Ok(Arc::new(Value::String(hex)))
+	}
+
+	pub fn std_hmac(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
+		if args.len() != 3 {
+			return Err(RuntimeError::InvalidIntrinsicArgs);
 		}
+		let message = helpers::extract_string(&args[0])?;
+		let key = helpers::extract_string(&args[1])?;
+		let algorithm = helpers::extract_string(&args[2])?;
+		let hex = hmac_hex(&algorithm, key.as_bytes(), message.as_bytes())?;
+		Ok(Arc::new(Value::String(hex)))
 	}
 }
 
@@ -298,42 +802,35 @@ mod advanced_ops {
 mod control_flow {
 	use super::*;
 
-	pub fn std_if(args: Vec<Arc<Value>>) -> Arc<Value> {
+	pub fn std_if(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 		if args.len() != 3 {
-			panic!("std_if expects 3 arguments: condition, true_value, false_value");
+			return Err(RuntimeError::InvalidIntrinsicArgs);
 		}
 		match &*args[0] {
 			Value::Boolean(condition) => {
 				if *condition {
-					args[1].clone()
+					Ok(args[1].clone())
 				} else {
-					args[2].clone()
+					Ok(args[2].clone())
 				}
 			},
-			_ => panic!("First argument of std_if must be a boolean"),
+			_ => Err(RuntimeError::IntrinsicTypeMismatch(
+				"first argument of std_if must be a boolean".to_string(),
+			)),
 		}
 	}
-
-	pub fn std_try(args: Vec<Arc<Value>>) -> Arc<Value> {
-		if args.len() != 2 {
-			panic!("std_try expects 2 arguments: expression, default_value");
-		}
-		// In a real implementation, you'd want to actually try evaluating the first argument
-		// and return the second if it fails. For now, we'll just return the first argument.
-		args[0].clone()
-	}
 }
 
 // Wrapper functions for arithmetic operations
 macro_rules! create_arithmetic_wrapper {
     ($name:ident, $func:path) => {
-        fn $name(args: Vec<Arc<Value>>) -> Arc<Value> {
+        fn $name(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
             if args.len() != 2 {
-                panic!(concat!(stringify!($name), " expects 2 arguments"));
+                return Err(RuntimeError::InvalidIntrinsicArgs);
             }
-            let n1 = helpers::extract_number(&args[0]);
-            let n2 = helpers::extract_number(&args[1]);
-            Arc::new(Value::Number($func(n1, n2)))
+            let n1 = helpers::extract_number(&args[0])?;
+            let n2 = helpers::extract_number(&args[1])?;
+            Ok(Arc::new(Value::Number($func(n1, n2)?)))
         }
     };
 }
@@ -348,49 +845,91 @@ create_arithmetic_wrapper!(std_sub_float_wrapper, arithmetic::std_sub_float);
 create_arithmetic_wrapper!(std_mul_float_wrapper, arithmetic::std_mul_float);
 create_arithmetic_wrapper!(std_div_float_wrapper, arithmetic::std_div_float);
 
+// Coercing arithmetic wrappers (the numeric tower)
+create_arithmetic_wrapper!(std_add_wrapper, arithmetic::std_add);
+create_arithmetic_wrapper!(std_sub_wrapper, arithmetic::std_sub);
+create_arithmetic_wrapper!(std_mul_wrapper, arithmetic::std_mul);
+create_arithmetic_wrapper!(std_div_wrapper, arithmetic::std_div);
+
+// A unary wrapper for single-argument `Number -> Number` functions.
+macro_rules! create_unary_number_wrapper {
+    ($name:ident, $func:path) => {
+        fn $name(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
+            if args.len() != 1 {
+                return Err(RuntimeError::InvalidIntrinsicArgs);
+            }
+            let n = helpers::extract_number(&args[0])?;
+            Ok(Arc::new(Value::Number($func(n)?)))
+        }
+    };
+}
+
+// Math wrappers
+create_unary_number_wrapper!(std_sqrt_wrapper, math::std_sqrt);
+create_unary_number_wrapper!(std_abs_wrapper, math::std_abs);
+create_unary_number_wrapper!(std_floor_wrapper, math::std_floor);
+create_unary_number_wrapper!(std_ceil_wrapper, math::std_ceil);
+create_unary_number_wrapper!(std_round_wrapper, math::std_round);
+create_unary_number_wrapper!(std_exp_wrapper, math::std_exp);
+create_unary_number_wrapper!(std_ln_wrapper, math::std_ln);
+create_unary_number_wrapper!(std_sin_wrapper, math::std_sin);
+create_unary_number_wrapper!(std_cos_wrapper, math::std_cos);
+create_unary_number_wrapper!(std_tan_wrapper, math::std_tan);
+create_arithmetic_wrapper!(std_pow_wrapper, math::std_pow);
+create_arithmetic_wrapper!(std_log_wrapper, math::std_log);
+
 // Wrapper functions for conversion operations
-fn std_int_to_float_wrapper(args: Vec<Arc<Value>>) -> Arc<Value> {
+fn std_int_to_float_wrapper(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 	if args.len() != 1 {
-		panic!("std_int_to_float expects 1 argument");
+		return Err(RuntimeError::InvalidIntrinsicArgs);
 	}
-	let n = helpers::extract_number(&args[0]);
-	Arc::new(Value::Number(conversion::std_int_to_float(n)))
+	let n = helpers::extract_number(&args[0])?;
+	Ok(Arc::new(Value::Number(conversion::std_int_to_float(n)?)))
 }
 
-fn std_float_to_int_wrapper(args: Vec<Arc<Value>>) -> Arc<Value> {
+fn std_float_to_int_wrapper(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 	if args.len() != 1 {
-		panic!("std_float_to_int expects 1 argument");
+		return Err(RuntimeError::InvalidIntrinsicArgs);
 	}
-	let n = helpers::extract_number(&args[0]);
-	Arc::new(Value::Number(conversion::std_float_to_int(n)))
+	let n = helpers::extract_number(&args[0])?;
+	Ok(Arc::new(Value::Number(conversion::std_float_to_int(n)?)))
 }
 
 // Wrapper functions for comparison operations
-fn std_eq_wrapper(args: Vec<Arc<Value>>) -> Arc<Value> {
+fn std_eq_wrapper(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 	if args.len() != 2 {
-		panic!("std_eq expects 2 arguments");
+		return Err(RuntimeError::InvalidIntrinsicArgs);
 	}
-	let n1 = helpers::extract_number(&args[0]);
-	let n2 = helpers::extract_number(&args[1]);
-	Arc::new(Value::Boolean(comparison::std_eq(n1, n2)))
+	let n1 = helpers::extract_number(&args[0])?;
+	let n2 = helpers::extract_number(&args[1])?;
+	Ok(Arc::new(Value::Boolean(comparison::std_eq(n1, n2)?)))
 }
 
-fn std_lt_wrapper(args: Vec<Arc<Value>>) -> Arc<Value> {
+fn std_lt_wrapper(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 	if args.len() != 2 {
-		panic!("std_lt expects 2 arguments");
+		return Err(RuntimeError::InvalidIntrinsicArgs);
 	}
-	let n1 = helpers::extract_number(&args[0]);
-	let n2 = helpers::extract_number(&args[1]);
-	Arc::new(Value::Boolean(comparison::std_lt(n1, n2)))
+	let n1 = helpers::extract_number(&args[0])?;
+	let n2 = helpers::extract_number(&args[1])?;
+	Ok(Arc::new(Value::Boolean(comparison::std_lt(n1, n2)?)))
 }
 
-fn std_gt_wrapper(args: Vec<Arc<Value>>) -> Arc<Value> {
+fn std_gt_wrapper(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 	if args.len() != 2 {
-		panic!("std_gt expects 2 arguments");
+		return Err(RuntimeError::InvalidIntrinsicArgs);
 	}
-	let n1 = helpers::extract_number(&args[0]);
-	let n2 = helpers::extract_number(&args[1]);
-	Arc::new(Value::Boolean(comparison::std_gt(n1, n2)))
+	let n1 = helpers::extract_number(&args[0])?;
+	let n2 = helpers::extract_number(&args[1])?;
+	Ok(Arc::new(Value::Boolean(comparison::std_gt(n1, n2)?)))
+}
+
+fn std_cmp_wrapper(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
+	if args.len() != 2 {
+		return Err(RuntimeError::InvalidIntrinsicArgs);
+	}
+	let n1 = helpers::extract_number(&args[0])?;
+	let n2 = helpers::extract_number(&args[1])?;
+	Ok(Arc::new(Value::Number(comparison::std_cmp(n1, n2)?)))
 }
 
 // Wrapper functions for bitwise operations
@@ -398,18 +937,18 @@ create_arithmetic_wrapper!(std_bitwise_and_wrapper, bitwise::std_bitwise_and);
 create_arithmetic_wrapper!(std_bitwise_or_wrapper, bitwise::std_bitwise_or);
 create_arithmetic_wrapper!(std_bitwise_xor_wrapper, bitwise::std_bitwise_xor);
 
-fn std_bitwise_not_wrapper(args: Vec<Arc<Value>>) -> Arc<Value> {
+fn std_bitwise_not_wrapper(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 	if args.len() != 1 {
-		panic!("std_bitwise_not expects 1 argument");
+		return Err(RuntimeError::InvalidIntrinsicArgs);
 	}
-	let n = helpers::extract_number(&args[0]);
-	Arc::new(Value::Number(bitwise::std_bitwise_not(n)))
+	let n = helpers::extract_number(&args[0])?;
+	Ok(Arc::new(Value::Number(bitwise::std_bitwise_not(n)?)))
 }
 
 /// StdLibLoader
 /// Load and manage standard library functions
 pub struct StdLibLoader {
-	loadable: FnvHashMap<String, VtcFn>,
+	loadable: FnvHashMap<String, RegisteredIntrinsic>,
 }
 
 #[macro_export]
@@ -421,62 +960,112 @@ macro_rules! register_function {
 
 impl StdLibLoader {
 	pub fn new() -> Self {
+		use ArgKind::{Any, Boolean, Float, Integer, String as Str};
+
 		let mut loadable = FnvHashMap::default();
 
+		let mut reg = |name: &str, signature: Signature, func: VtcFn| {
+			loadable.insert(name.to_string(), RegisteredIntrinsic { signature, func });
+		};
+
 		// Arithmetic operations
-		loadable.insert("std_add_int".to_string(), Box::new(std_add_int_wrapper) as VtcFn);
-		loadable.insert("std_sub_int".to_string(), Box::new(std_sub_int_wrapper) as VtcFn);
-		loadable.insert("std_mul_int".to_string(), Box::new(std_mul_int_wrapper) as VtcFn);
-		loadable.insert("std_div_int".to_string(), Box::new(std_div_int_wrapper) as VtcFn);
-		loadable.insert("std_mod_int".to_string(), Box::new(std_mod_int_wrapper) as VtcFn);
-		loadable.insert("std_add_float".to_string(), Box::new(std_add_float_wrapper) as VtcFn);
-		loadable.insert("std_sub_float".to_string(), Box::new(std_sub_float_wrapper) as VtcFn);
-		loadable.insert("std_mul_float".to_string(), Box::new(std_mul_float_wrapper) as VtcFn);
-		loadable.insert("std_div_float".to_string(), Box::new(std_div_float_wrapper) as VtcFn);
+		reg("std_add_int", Signature::exact(&[Integer, Integer]), Box::new(std_add_int_wrapper));
+		reg("std_sub_int", Signature::exact(&[Integer, Integer]), Box::new(std_sub_int_wrapper));
+		reg("std_mul_int", Signature::exact(&[Integer, Integer]), Box::new(std_mul_int_wrapper));
+		reg("std_div_int", Signature::exact(&[Integer, Integer]), Box::new(std_div_int_wrapper));
+		reg("std_mod_int", Signature::exact(&[Integer, Integer]), Box::new(std_mod_int_wrapper));
+		reg("std_add_float", Signature::exact(&[Float, Float]), Box::new(std_add_float_wrapper));
+		reg("std_sub_float", Signature::exact(&[Float, Float]), Box::new(std_sub_float_wrapper));
+		reg("std_mul_float", Signature::exact(&[Float, Float]), Box::new(std_mul_float_wrapper));
+		reg("std_div_float", Signature::exact(&[Float, Float]), Box::new(std_div_float_wrapper));
+
+		// Coercing arithmetic operations: accept any mix of integer and float and
+		// promote per the numeric tower, so configs need not pre-convert operands.
+		reg("std_add", Signature::exact(&[Any, Any]), Box::new(std_add_wrapper));
+		reg("std_sub", Signature::exact(&[Any, Any]), Box::new(std_sub_wrapper));
+		reg("std_mul", Signature::exact(&[Any, Any]), Box::new(std_mul_wrapper));
+		reg("std_div", Signature::exact(&[Any, Any]), Box::new(std_div_wrapper));
+
+		// Math operations
+		reg("std_sqrt", Signature::exact(&[Any]), Box::new(std_sqrt_wrapper));
+		reg("std_abs", Signature::exact(&[Any]), Box::new(std_abs_wrapper));
+		reg("std_floor", Signature::exact(&[Any]), Box::new(std_floor_wrapper));
+		reg("std_ceil", Signature::exact(&[Any]), Box::new(std_ceil_wrapper));
+		reg("std_round", Signature::exact(&[Any]), Box::new(std_round_wrapper));
+		reg("std_exp", Signature::exact(&[Any]), Box::new(std_exp_wrapper));
+		reg("std_ln", Signature::exact(&[Any]), Box::new(std_ln_wrapper));
+		reg("std_sin", Signature::exact(&[Any]), Box::new(std_sin_wrapper));
+		reg("std_cos", Signature::exact(&[Any]), Box::new(std_cos_wrapper));
+		reg("std_tan", Signature::exact(&[Any]), Box::new(std_tan_wrapper));
+		reg("std_pow", Signature::exact(&[Any, Any]), Box::new(std_pow_wrapper));
+		reg("std_log", Signature::exact(&[Any, Any]), Box::new(std_log_wrapper));
 
 		// Conversion operations
-		loadable.insert("std_int_to_float".to_string(), Box::new(std_int_to_float_wrapper) as VtcFn);
-		loadable.insert("std_float_to_int".to_string(), Box::new(std_float_to_int_wrapper) as VtcFn);
+		reg("std_int_to_float", Signature::exact(&[Integer]), Box::new(std_int_to_float_wrapper));
+		reg("std_float_to_int", Signature::exact(&[Float]), Box::new(std_float_to_int_wrapper));
 
-		// Comparison operations
-		loadable.insert("std_eq".to_string(), Box::new(std_eq_wrapper) as VtcFn);
-		loadable.insert("std_lt".to_string(), Box::new(std_lt_wrapper) as VtcFn);
-		loadable.insert("std_gt".to_string(), Box::new(std_gt_wrapper) as VtcFn);
+		// Comparison operations (type coercion happens inside the functions)
+		reg("std_eq", Signature::exact(&[Any, Any]), Box::new(std_eq_wrapper));
+		reg("std_lt", Signature::exact(&[Any, Any]), Box::new(std_lt_wrapper));
+		reg("std_gt", Signature::exact(&[Any, Any]), Box::new(std_gt_wrapper));
+		reg("std_cmp", Signature::exact(&[Any, Any]), Box::new(std_cmp_wrapper));
 
 		// Bitwise operations
-		loadable.insert("std_bitwise_and".to_string(), Box::new(std_bitwise_and_wrapper) as VtcFn);
-		loadable.insert("std_bitwise_or".to_string(), Box::new(std_bitwise_or_wrapper) as VtcFn);
-		loadable.insert("std_bitwise_xor".to_string(), Box::new(std_bitwise_xor_wrapper) as VtcFn);
-		loadable.insert("std_bitwise_not".to_string(), Box::new(std_bitwise_not_wrapper) as VtcFn);
+		reg("std_bitwise_and", Signature::exact(&[Integer, Integer]), Box::new(std_bitwise_and_wrapper));
+		reg("std_bitwise_or", Signature::exact(&[Integer, Integer]), Box::new(std_bitwise_or_wrapper));
+		reg("std_bitwise_xor", Signature::exact(&[Integer, Integer]), Box::new(std_bitwise_xor_wrapper));
+		reg("std_bitwise_not", Signature::exact(&[Integer]), Box::new(std_bitwise_not_wrapper));
 
 		// String operations
-		loadable.insert("std_to_uppercase".to_string(), Box::new(string_ops::std_to_uppercase) as VtcFn);
-		loadable.insert("std_to_lowercase".to_string(), Box::new(string_ops::std_to_lowercase) as VtcFn);
-		loadable.insert("std_substring".to_string(), Box::new(string_ops::std_substring) as VtcFn);
-		loadable.insert("std_concat".to_string(), Box::new(string_ops::std_concat) as VtcFn);
-		loadable.insert("std_replace".to_string(), Box::new(string_ops::std_replace) as VtcFn);
+		reg("std_to_uppercase", Signature::exact(&[Str]), Box::new(string_ops::std_to_uppercase));
+		reg("std_to_lowercase", Signature::exact(&[Str]), Box::new(string_ops::std_to_lowercase));
+		reg("std_substring", Signature::exact(&[Str, Integer, Integer]), Box::new(string_ops::std_substring));
+		reg("std_concat", Signature::variadic(1, None, &[Str]), Box::new(string_ops::std_concat));
+		reg("std_replace", Signature::exact(&[Str, Str, Str]), Box::new(string_ops::std_replace));
 
 		// Advanced operations
-		loadable.insert("std_base64_encode".to_string(), Box::new(advanced_ops::std_base64_encode) as VtcFn);
-		loadable.insert("std_base64_decode".to_string(), Box::new(advanced_ops::std_base64_decode) as VtcFn);
-		loadable.insert("std_hash".to_string(), Box::new(advanced_ops::std_hash) as VtcFn);
+		reg("std_base64_encode", Signature::variadic(1, Some(2), &[Str]), Box::new(advanced_ops::std_base64_encode));
+		reg("std_base64_decode", Signature::variadic(1, Some(2), &[Str]), Box::new(advanced_ops::std_base64_decode));
+		reg("std_hash", Signature::exact(&[Str, Str]), Box::new(advanced_ops::std_hash));
+		reg("std_hmac", Signature::exact(&[Str, Str, Str]), Box::new(advanced_ops::std_hmac));
 
-		// Control flow
-		loadable.insert("std_if".to_string(), Box::new(control_flow::std_if) as VtcFn);
-		loadable.insert("std_try".to_string(), Box::new(control_flow::std_try) as VtcFn);
+		// Control flow. `std_try` is handled specially in the resolver
+		// (alongside `unwrap`) because genuine error recovery must intercept the
+		// first argument *before* it is eagerly resolved, which the generic
+		// dispatch path cannot do.
+		reg("std_if", Signature::exact(&[Boolean, Any, Any]), Box::new(control_flow::std_if));
 
 		Self { loadable }
 	}
 
 	pub fn get_function(&self, name: &str) -> Option<&VtcFn> {
-		self.loadable.get(name)
+		self.loadable.get(name).map(|reg| &reg.func)
+	}
+
+	/// Returns the validation signature registered for `name`, if any.
+	pub fn get_signature(&self, name: &str) -> Option<&Signature> {
+		self.loadable.get(name).map(|reg| &reg.signature)
 	}
 
+	/// Registers a custom function with the fully [permissive](Signature::permissive)
+	/// signature, keeping the historic `(name, func)` shape for existing callers.
 	pub fn register_function(&mut self, name: String, function: VtcFn) -> Result<()> {
+		self.register_intrinsic(name, Signature::permissive(), function)
+	}
+
+	/// Registers a custom intrinsic together with the [`Signature`] that drives
+	/// its argument validation, so downstream functions get the same arity and
+	/// type checking as the built-ins. Names starting with `std` are reserved.
+	pub fn register_intrinsic(
+		&mut self,
+		name: String,
+		signature: Signature,
+		function: VtcFn,
+	) -> Result<()> {
 		if name.starts_with("std") {
 			return Err(anyhow!("User defined functions cannot start with `std`.".to_string()))
 		}
-		self.loadable.insert(name, function);
+		self.loadable.insert(name, RegisteredIntrinsic { signature, func: function });
 		Ok(())
 	}
 }
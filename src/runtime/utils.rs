@@ -47,6 +47,9 @@ impl Runtime {
             Value::Number(Number::Integer(i)) => Ok(*i),
             Value::Number(Number::Binary(b)) => Ok(*b),
             Value::Number(Number::Hexadecimal(h)) => Ok(*h),
+            Value::Number(Number::BigInt(b)) => b
+                .to_i64()
+                .ok_or_else(|| RuntimeError::ConversionError("integer too large for i64".to_string())),
             _ => Err(RuntimeError::TypeError("Expected integer".to_string())),
         })
     }
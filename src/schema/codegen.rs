@@ -0,0 +1,226 @@
+//! Rust codegen for schemas.
+//!
+//! Emits one struct per namespace with typed fields and a
+//! `from_runtime(&Runtime) -> Result<Self, SchemaError>` constructor, so
+//! downstream code gets compile-time-checked field access instead of
+//! `get_integer("ns", "var")`. Intended to be driven from a `build.rs` or the
+//! `vtc-schemagen` binary.
+
+use super::{NamespaceSchema, Schema, SchemaType};
+
+/// Generates a Rust module source string for `schema`.
+///
+/// The output depends only on `vtc` and `std`, and assumes it will be placed in
+/// a crate that has `vtc` as a dependency.
+pub fn generate_rust(schema: &Schema) -> String {
+	let mut out = String::new();
+	out.push_str("// @generated by vtc schema codegen — do not edit by hand.\n");
+	out.push_str("use vtc::runtime::Runtime;\n");
+	out.push_str("use vtc::schema::SchemaError;\n\n");
+
+	for namespace in &schema.namespaces {
+		generate_struct(namespace, &mut out);
+	}
+
+	out
+}
+
+fn generate_struct(namespace: &NamespaceSchema, out: &mut String) {
+	let struct_name = to_pascal_case(&namespace.name);
+
+	out.push_str("#[derive(Debug, Clone)]\n");
+	out.push_str(&format!("pub struct {} {{\n", struct_name));
+	for field in &namespace.fields {
+		out.push_str(&format!(
+			"    pub {}: {},\n",
+			sanitize_ident(&field.name),
+			rust_type(&field.ty)
+		));
+	}
+	out.push_str("}\n\n");
+
+	out.push_str(&format!("impl {} {{\n", struct_name));
+	out.push_str(
+		"    pub fn from_runtime(runtime: &Runtime) -> Result<Self, SchemaError> {\n",
+	);
+	out.push_str("        Ok(Self {\n");
+	for field in &namespace.fields {
+		out.push_str(&format!(
+			"            {}: {},\n",
+			sanitize_ident(&field.name),
+			field_extractor(&namespace.name, &field.name, &field.ty)
+		));
+	}
+	out.push_str("        })\n");
+	out.push_str("    }\n");
+	out.push_str("}\n\n");
+}
+
+/// The Rust type for a schema type. Reference values keep their resolved
+/// `vtc::value::Value`, since their concrete shape isn't known statically.
+fn rust_type(ty: &SchemaType) -> String {
+	match ty {
+		SchemaType::String => "String".to_string(),
+		SchemaType::Integer => "i64".to_string(),
+		SchemaType::Float => "f64".to_string(),
+		SchemaType::Boolean => "bool".to_string(),
+		SchemaType::List(inner) => format!("Vec<{}>", rust_type(inner)),
+		SchemaType::Dict(inner) => {
+			format!("std::collections::HashMap<String, {}>", rust_type(inner))
+		}
+		SchemaType::Reference(_) => "vtc::value::Value".to_string(),
+		SchemaType::Optional(inner) => format!("Option<{}>", rust_type(inner)),
+	}
+}
+
+/// An expression that extracts the field from `runtime` as the field's Rust
+/// type, propagating a `SchemaError` with `?`, for use inside `Ok(Self { .. })`.
+///
+/// Generation covers scalars, arbitrarily nested `List`/`Dict`/`Optional`
+/// combinations, and references (which keep their resolved `Value` since its
+/// concrete shape isn't known statically).
+fn field_extractor(namespace: &str, variable: &str, ty: &SchemaType) -> String {
+	match ty {
+		SchemaType::String => scalar_getter(namespace, variable, "get_string"),
+		SchemaType::Integer => scalar_getter(namespace, variable, "get_integer"),
+		SchemaType::Float => scalar_getter(namespace, variable, "get_float"),
+		SchemaType::Boolean => scalar_getter(namespace, variable, "get_boolean"),
+		SchemaType::Reference(_) => format!(
+			"runtime.get_value({:?}, {:?}, &[]).map(|v| (*v).clone()).map_err(|e| {})?",
+			namespace,
+			variable,
+			resolution_mapper(namespace, variable)
+		),
+		SchemaType::Optional(inner) => format!(
+			"match runtime.get_value({:?}, {:?}, &[]) {{ Ok(v) if matches!(&*v, vtc::value::Value::Nil) => None, Ok(_) => Some({}), Err(_) => None }}",
+			namespace,
+			variable,
+			field_extractor(namespace, variable, inner)
+		),
+		// The target field type is `Vec<T>`/`HashMap<String, T>` (see
+		// `rust_type`), so the raw resolved `Value` has to actually be
+		// destructured into that shape, not just cloned — `value_extractor`
+		// recurses to handle nested List/Dict/Optional element types too.
+		SchemaType::List(inner) => format!(
+			"match &*runtime.get_value({namespace:?}, {variable:?}, &[]).map_err(|e| {mapper})? {{ \
+				vtc::value::Value::List(items) => items.iter().map(|v| {elem}).collect::<Result<Vec<_>, String>>(), \
+				other => Err(format!(\"expected a list, got {{:?}}\", other)), \
+			}}.map_err(|message| {mapper_msg})?",
+			namespace = namespace,
+			variable = variable,
+			mapper = resolution_mapper(namespace, variable),
+			elem = value_extractor("v", inner),
+			mapper_msg = resolution_mapper_with_message(namespace, variable),
+		),
+		SchemaType::Dict(inner) => format!(
+			"match &*runtime.get_value({namespace:?}, {variable:?}, &[]).map_err(|e| {mapper})? {{ \
+				vtc::value::Value::Map(entries) => entries.iter().map(|(k, v)| {elem}.map(|value| (k.to_string(), value))).collect::<Result<std::collections::HashMap<_, _>, String>>(), \
+				other => Err(format!(\"expected a dict, got {{:?}}\", other)), \
+			}}.map_err(|message| {mapper_msg})?",
+			namespace = namespace,
+			variable = variable,
+			mapper = resolution_mapper(namespace, variable),
+			elem = value_extractor("v", inner),
+			mapper_msg = resolution_mapper_with_message(namespace, variable),
+		),
+	}
+}
+
+/// An expression converting `binding` (a `&vtc::value::Value`) into `ty`'s
+/// Rust type, evaluating to `Result<_, String>`. Used for `List`/`Dict`
+/// elements, where there's no `namespace`/`variable` to thread through a
+/// `SchemaError` — the caller wraps the plain `String` message once the whole
+/// collection has been gathered.
+fn value_extractor(binding: &str, ty: &SchemaType) -> String {
+	match ty {
+		SchemaType::String => format!(
+			"match {b} {{ vtc::value::Value::String(s) => Ok(s.clone()), other => Err(format!(\"expected a string, got {{:?}}\", other)) }}",
+			b = binding
+		),
+		SchemaType::Integer => format!(
+			"match {b} {{ vtc::value::Value::Number(n) => n.as_i64().ok_or_else(|| \"integer too large for i64\".to_string()), other => Err(format!(\"expected an integer, got {{:?}}\", other)) }}",
+			b = binding
+		),
+		SchemaType::Float => format!(
+			"match {b} {{ vtc::value::Value::Number(vtc::value::Number::Float(f)) => Ok(*f), other => Err(format!(\"expected a float, got {{:?}}\", other)) }}",
+			b = binding
+		),
+		SchemaType::Boolean => format!(
+			"match {b} {{ vtc::value::Value::Boolean(b) => Ok(*b), other => Err(format!(\"expected a boolean, got {{:?}}\", other)) }}",
+			b = binding
+		),
+		SchemaType::List(inner) => format!(
+			"match {b} {{ vtc::value::Value::List(items) => items.iter().map(|v| {elem}).collect::<Result<Vec<_>, String>>(), other => Err(format!(\"expected a list, got {{:?}}\", other)) }}",
+			b = binding,
+			elem = value_extractor("v", inner),
+		),
+		SchemaType::Dict(inner) => format!(
+			"match {b} {{ vtc::value::Value::Map(entries) => entries.iter().map(|(k, v)| {elem}.map(|value| (k.to_string(), value))).collect::<Result<std::collections::HashMap<_, _>, String>>(), other => Err(format!(\"expected a dict, got {{:?}}\", other)) }}",
+			b = binding,
+			elem = value_extractor("v", inner),
+		),
+		SchemaType::Optional(inner) => format!(
+			"match {b} {{ vtc::value::Value::Nil => Ok(None), v => ({elem}).map(Some) }}",
+			b = binding,
+			elem = value_extractor("v", inner),
+		),
+		SchemaType::Reference(_) => format!("Ok({}.clone())", binding),
+	}
+}
+
+fn scalar_getter(namespace: &str, variable: &str, getter: &str) -> String {
+	format!(
+		"runtime.{}({:?}, {:?}).map_err(|e| {})?",
+		getter,
+		namespace,
+		variable,
+		resolution_mapper(namespace, variable)
+	)
+}
+
+fn resolution_mapper(namespace: &str, variable: &str) -> String {
+	format!(
+		"SchemaError::Resolution {{ namespace: {:?}.to_string(), variable: {:?}.to_string(), message: e.to_string() }}",
+		namespace, variable
+	)
+}
+
+/// Same as [`resolution_mapper`], but for a `.map_err(|message| ...)` closure
+/// over a plain `String` (the error type `value_extractor` collects into)
+/// rather than an error type with a `to_string()` conversion.
+fn resolution_mapper_with_message(namespace: &str, variable: &str) -> String {
+	format!(
+		"SchemaError::Resolution {{ namespace: {:?}.to_string(), variable: {:?}.to_string(), message }}",
+		namespace, variable
+	)
+}
+
+/// Converts a (possibly dotted) namespace name into a PascalCase struct name.
+fn to_pascal_case(name: &str) -> String {
+	name.split(|c: char| c == '.' || c == '_' || c == '-')
+		.filter(|segment| !segment.is_empty())
+		.map(|segment| {
+			let mut chars = segment.chars();
+			match chars.next() {
+				Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+				None => String::new(),
+			}
+		})
+		.collect()
+}
+
+/// Escapes a field name that collides with a Rust keyword using the raw-
+/// identifier syntax.
+fn sanitize_ident(name: &str) -> String {
+	const KEYWORDS: &[&str] = &[
+		"as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn",
+		"for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+		"return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+		"where", "while", "async", "await", "dyn",
+	];
+	if KEYWORDS.contains(&name) {
+		format!("r#{}", name)
+	} else {
+		name.to_string()
+	}
+}
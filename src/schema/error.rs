@@ -0,0 +1,79 @@
+//! Errors produced while validating a [`Runtime`](crate::runtime::Runtime)
+//! against a [`Schema`](super::Schema).
+
+use std::error::Error;
+use std::fmt;
+
+/// A single mismatch between a runtime and a schema. Validation collects every
+/// error in one pass rather than bailing on the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+	/// A namespace declared by the schema is absent from the runtime.
+	MissingNamespace(String),
+	/// A variable declared by the schema is absent from its namespace.
+	MissingVariable { namespace: String, variable: String },
+	/// A variable exists but holds the wrong kind of value.
+	TypeMismatch {
+		namespace: String,
+		variable: String,
+		expected: String,
+		found: String,
+	},
+	/// A `Dict<String, T>` variable did not have an even-length key/value list.
+	OddLengthDict { namespace: String, variable: String },
+	/// A `Reference` variable points at a namespace/variable that does not exist.
+	DanglingReference {
+		namespace: String,
+		variable: String,
+		target: String,
+	},
+	/// Resolving a variable failed for a reason other than simple absence
+	/// (circular reference, intrinsic failure, ...).
+	Resolution {
+		namespace: String,
+		variable: String,
+		message: String,
+	},
+}
+
+impl fmt::Display for SchemaError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SchemaError::MissingNamespace(ns) => write!(f, "missing namespace `{}`", ns),
+			SchemaError::MissingVariable { namespace, variable } => {
+				write!(f, "missing variable `{}.{}`", namespace, variable)
+			}
+			SchemaError::TypeMismatch {
+				namespace,
+				variable,
+				expected,
+				found,
+			} => write!(
+				f,
+				"`{}.{}`: expected {}, found {}",
+				namespace, variable, expected, found
+			),
+			SchemaError::OddLengthDict { namespace, variable } => write!(
+				f,
+				"`{}.{}`: dict requires an even-length key/value list",
+				namespace, variable
+			),
+			SchemaError::DanglingReference {
+				namespace,
+				variable,
+				target,
+			} => write!(
+				f,
+				"`{}.{}`: reference to `{}` does not resolve",
+				namespace, variable, target
+			),
+			SchemaError::Resolution {
+				namespace,
+				variable,
+				message,
+			} => write!(f, "`{}.{}`: {}", namespace, variable, message),
+		}
+	}
+}
+
+impl Error for SchemaError {}
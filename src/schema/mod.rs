@@ -0,0 +1,445 @@
+//! Schema subsystem for validating and code-generating typed accessors.
+//!
+//! Callers normally reach into a [`Runtime`](crate::runtime::Runtime) with
+//! stringly-typed helpers such as `get_integer`/`get_string`/`as_dict`, so a
+//! typo or wrong type only surfaces at the call site. Borrowing the idea of
+//! Preserves Schema — a schema language that both validates data and compiles
+//! to native types — this module lets users declare the expected shape of one
+//! or more namespaces, validate a whole runtime against it in one pass
+//! ([`Runtime::validate`]), and generate a typed Rust struct per namespace
+//! ([`codegen`]).
+//!
+//! # Schema text format
+//!
+//! ```text
+//! namespace app {
+//!     host: String
+//!     port: Integer
+//!     tags: List<String>
+//!     settings: Dict<String, Integer>
+//!     fallback: Reference
+//!     primary: Reference<db>
+//!     nickname: Optional<String>
+//! }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::runtime::error::RuntimeError;
+use crate::runtime::Runtime;
+use crate::value::{Number, Value};
+
+pub mod codegen;
+pub mod error;
+
+pub use error::SchemaError;
+
+/// A declared type for a single variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaType {
+	String,
+	Integer,
+	Float,
+	Boolean,
+	/// A homogeneous list of `T`.
+	List(Box<SchemaType>),
+	/// A list of alternating string keys and `T` values, matching the
+	/// even-length convention used by [`Runtime::as_dict`].
+	Dict(Box<SchemaType>),
+	/// A reference value, optionally constrained to a target namespace.
+	Reference(Option<String>),
+	/// An optional `T`: a missing variable or a `Nil` value is accepted.
+	Optional(Box<SchemaType>),
+}
+
+impl fmt::Display for SchemaType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SchemaType::String => write!(f, "String"),
+			SchemaType::Integer => write!(f, "Integer"),
+			SchemaType::Float => write!(f, "Float"),
+			SchemaType::Boolean => write!(f, "Boolean"),
+			SchemaType::List(inner) => write!(f, "List<{}>", inner),
+			SchemaType::Dict(inner) => write!(f, "Dict<String, {}>", inner),
+			SchemaType::Reference(Some(target)) => write!(f, "Reference<{}>", target),
+			SchemaType::Reference(None) => write!(f, "Reference"),
+			SchemaType::Optional(inner) => write!(f, "Optional<{}>", inner),
+		}
+	}
+}
+
+/// A single variable declaration within a namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+	pub name: String,
+	pub ty: SchemaType,
+}
+
+/// The expected shape of one namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceSchema {
+	pub name: String,
+	pub fields: Vec<FieldSchema>,
+}
+
+/// A complete schema over one or more namespaces.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Schema {
+	pub namespaces: Vec<NamespaceSchema>,
+}
+
+impl Schema {
+	/// Parses a schema from its textual form (see the [module docs](self)).
+	pub fn parse(input: &str) -> Result<Schema, String> {
+		parse_schema(input)
+	}
+}
+
+impl Runtime {
+	/// Validates the runtime against `schema`, collecting every mismatch in a
+	/// single pass: missing namespaces and variables, wrong value variants,
+	/// odd-length dicts and dangling references.
+	///
+	/// # Errors
+	///
+	/// Returns the full list of [`SchemaError`]s when validation fails.
+	pub fn validate(&self, schema: &Schema) -> Result<(), Vec<SchemaError>> {
+		let mut errors = Vec::new();
+
+		for ns in &schema.namespaces {
+			let ns_key = Arc::new(ns.name.clone());
+			let variables = match self.namespaces.get(&ns_key) {
+				Some(variables) => variables,
+				None => {
+					errors.push(SchemaError::MissingNamespace(ns.name.clone()));
+					continue;
+				}
+			};
+
+			for field in &ns.fields {
+				let raw = variables.get(&Arc::new(field.name.clone()));
+				self.check_field(&ns.name, field, raw, &mut errors);
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(errors)
+		}
+	}
+
+	fn check_field(
+		&self,
+		namespace: &str,
+		field: &FieldSchema,
+		raw: Option<&Arc<Value>>,
+		errors: &mut Vec<SchemaError>,
+	) {
+		// An optional field absorbs both an absent variable and a Nil value.
+		if let SchemaType::Optional(inner) = &field.ty {
+			match raw {
+				None => return,
+				Some(value) if matches!(&**value, Value::Nil) => return,
+				Some(_) => {
+					let inner_field = FieldSchema {
+						name: field.name.clone(),
+						ty: (**inner).clone(),
+					};
+					self.check_field(namespace, &inner_field, raw, errors);
+					return;
+				}
+			}
+		}
+
+		let raw = match raw {
+			Some(value) => value,
+			None => {
+				errors.push(SchemaError::MissingVariable {
+					namespace: namespace.to_string(),
+					variable: field.name.clone(),
+				});
+				return;
+			}
+		};
+
+		// References are inspected in their raw, unresolved form so the target
+		// constraint and dangling check are meaningful.
+		if let SchemaType::Reference(target) = &field.ty {
+			self.check_reference(namespace, &field.name, raw, target.as_deref(), errors);
+			return;
+		}
+
+		// Everything else is resolved first, then checked by variant.
+		match self.get_value(namespace, &field.name, &[]) {
+			Ok(resolved) => self.check_type(namespace, &field.name, &resolved, &field.ty, errors),
+			Err(e) => errors.push(resolution_error(namespace, &field.name, &e)),
+		}
+	}
+
+	fn check_reference(
+		&self,
+		namespace: &str,
+		variable: &str,
+		raw: &Arc<Value>,
+		target: Option<&str>,
+		errors: &mut Vec<SchemaError>,
+	) {
+		let reference = match &**raw {
+			Value::Reference(reference) => reference,
+			other => {
+				errors.push(SchemaError::TypeMismatch {
+					namespace: namespace.to_string(),
+					variable: variable.to_string(),
+					expected: "Reference".to_string(),
+					found: value_kind(other).to_string(),
+				});
+				return;
+			}
+		};
+
+		if let Some(target) = target {
+			let actual = reference.namespace.as_ref().map(|ns| ns.as_str());
+			if actual != Some(target) {
+				errors.push(SchemaError::TypeMismatch {
+					namespace: namespace.to_string(),
+					variable: variable.to_string(),
+					expected: format!("Reference<{}>", target),
+					found: format!("Reference<{}>", actual.unwrap_or("?")),
+				});
+			}
+		}
+
+		if let Err(RuntimeError::VariableNotFound(_) | RuntimeError::NamespaceNotFound(_)) =
+			self.get_value(namespace, variable, &[])
+		{
+			let dangling = format!(
+				"{}.{}",
+				reference
+					.namespace
+					.as_ref()
+					.map(|ns| ns.as_str())
+					.unwrap_or(namespace),
+				reference.variable
+			);
+			errors.push(SchemaError::DanglingReference {
+				namespace: namespace.to_string(),
+				variable: variable.to_string(),
+				target: dangling,
+			});
+		}
+	}
+
+	fn check_type(
+		&self,
+		namespace: &str,
+		variable: &str,
+		value: &Value,
+		ty: &SchemaType,
+		errors: &mut Vec<SchemaError>,
+	) {
+		match ty {
+			SchemaType::String => self.expect(namespace, variable, value, ty, errors, |v| {
+				matches!(v, Value::String(_))
+			}),
+			SchemaType::Integer => self.expect(namespace, variable, value, ty, errors, |v| {
+				matches!(
+					v,
+					Value::Number(
+						Number::Integer(_)
+							| Number::Binary(_)
+							| Number::Hexadecimal(_)
+							| Number::BigInt(_)
+					)
+				)
+			}),
+			SchemaType::Float => self.expect(namespace, variable, value, ty, errors, |v| {
+				matches!(v, Value::Number(Number::Float(_)))
+			}),
+			SchemaType::Boolean => self.expect(namespace, variable, value, ty, errors, |v| {
+				matches!(v, Value::Boolean(_))
+			}),
+			SchemaType::List(inner) => match value {
+				Value::List(items) => {
+					for item in items.iter() {
+						self.check_type(namespace, variable, item, inner, errors);
+					}
+				}
+				other => errors.push(type_mismatch(namespace, variable, ty, other)),
+			},
+			SchemaType::Dict(inner) => match value {
+				Value::List(items) => {
+					if items.len() % 2 != 0 {
+						errors.push(SchemaError::OddLengthDict {
+							namespace: namespace.to_string(),
+							variable: variable.to_string(),
+						});
+						return;
+					}
+					for chunk in items.chunks(2) {
+						if !matches!(&chunk[0], Value::String(_)) {
+							errors.push(SchemaError::TypeMismatch {
+								namespace: namespace.to_string(),
+								variable: variable.to_string(),
+								expected: "String (dict key)".to_string(),
+								found: value_kind(&chunk[0]).to_string(),
+							});
+						}
+						self.check_type(namespace, variable, &chunk[1], inner, errors);
+					}
+				}
+				other => errors.push(type_mismatch(namespace, variable, ty, other)),
+			},
+			// References and optionals are handled before resolution in
+			// `check_field`; reaching here means a nested occurrence, checked
+			// structurally.
+			SchemaType::Reference(_) => self.expect(namespace, variable, value, ty, errors, |v| {
+				matches!(v, Value::Reference(_))
+			}),
+			SchemaType::Optional(inner) => {
+				if !matches!(value, Value::Nil) {
+					self.check_type(namespace, variable, value, inner, errors);
+				}
+			}
+		}
+	}
+
+	fn expect(
+		&self,
+		namespace: &str,
+		variable: &str,
+		value: &Value,
+		ty: &SchemaType,
+		errors: &mut Vec<SchemaError>,
+		pred: impl Fn(&Value) -> bool,
+	) {
+		if !pred(value) {
+			errors.push(type_mismatch(namespace, variable, ty, value));
+		}
+	}
+}
+
+fn type_mismatch(namespace: &str, variable: &str, ty: &SchemaType, found: &Value) -> SchemaError {
+	SchemaError::TypeMismatch {
+		namespace: namespace.to_string(),
+		variable: variable.to_string(),
+		expected: ty.to_string(),
+		found: value_kind(found).to_string(),
+	}
+}
+
+fn resolution_error(namespace: &str, variable: &str, error: &RuntimeError) -> SchemaError {
+	match error {
+		RuntimeError::VariableNotFound(_) | RuntimeError::NamespaceNotFound(_) => {
+			SchemaError::MissingVariable {
+				namespace: namespace.to_string(),
+				variable: variable.to_string(),
+			}
+		}
+		other => SchemaError::Resolution {
+			namespace: namespace.to_string(),
+			variable: variable.to_string(),
+			message: other.to_string(),
+		},
+	}
+}
+
+/// A short, stable name for a value's kind, used in mismatch messages.
+fn value_kind(value: &Value) -> &'static str {
+	match value {
+		Value::Intrinsic(_) => "intrinsic",
+		Value::String(_) => "string",
+		Value::Number(_) => "number",
+		Value::Boolean(_) => "boolean",
+		Value::Nil => "nil",
+		Value::List(_) => "list",
+		Value::Map(_) => "map",
+		Value::Reference(_) => "reference",
+		Value::Expr(_) => "expression",
+	}
+}
+
+/// Hand-written recursive parser for the schema text format. Kept deliberately
+/// small: the DSL is line-oriented and whitespace-insensitive within a line.
+fn parse_schema(input: &str) -> Result<Schema, String> {
+	let mut namespaces = Vec::new();
+	let mut lines = input.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#'));
+	let mut current = lines.next();
+
+	while let Some(line) = current {
+		let header = line
+			.strip_prefix("namespace")
+			.map(str::trim)
+			.ok_or_else(|| format!("expected `namespace`, found `{}`", line))?;
+		let name = header
+			.strip_suffix('{')
+			.map(str::trim)
+			.ok_or_else(|| format!("namespace header must end with `{{`: `{}`", line))?
+			.to_string();
+		if name.is_empty() {
+			return Err("namespace is missing a name".to_string());
+		}
+
+		let mut fields = Vec::new();
+		loop {
+			current = lines.next();
+			let line = current.ok_or_else(|| format!("namespace `{}` is not closed", name))?;
+			if line == "}" {
+				break;
+			}
+			fields.push(parse_field(line)?);
+		}
+
+		namespaces.push(NamespaceSchema { name, fields });
+		current = lines.next();
+	}
+
+	Ok(Schema { namespaces })
+}
+
+fn parse_field(line: &str) -> Result<FieldSchema, String> {
+	let (name, ty) = line
+		.split_once(':')
+		.ok_or_else(|| format!("field must be `name: Type`: `{}`", line))?;
+	Ok(FieldSchema {
+		name: name.trim().to_string(),
+		ty: parse_type(ty.trim())?,
+	})
+}
+
+fn parse_type(text: &str) -> Result<SchemaType, String> {
+	match text {
+		"String" => Ok(SchemaType::String),
+		"Integer" => Ok(SchemaType::Integer),
+		"Float" => Ok(SchemaType::Float),
+		"Boolean" => Ok(SchemaType::Boolean),
+		"Reference" => Ok(SchemaType::Reference(None)),
+		_ => {
+			if let Some(inner) = wrapped(text, "List") {
+				Ok(SchemaType::List(Box::new(parse_type(inner)?)))
+			} else if let Some(inner) = wrapped(text, "Optional") {
+				Ok(SchemaType::Optional(Box::new(parse_type(inner)?)))
+			} else if let Some(inner) = wrapped(text, "Reference") {
+				Ok(SchemaType::Reference(Some(inner.trim().to_string())))
+			} else if let Some(inner) = wrapped(text, "Dict") {
+				let value = inner
+					.split_once(',')
+					.map(|(key, value)| (key.trim(), value.trim()))
+					.filter(|(key, _)| *key == "String")
+					.map(|(_, value)| value)
+					.ok_or_else(|| format!("Dict key type must be String: `{}`", text))?;
+				Ok(SchemaType::Dict(Box::new(parse_type(value)?)))
+			} else {
+				Err(format!("unknown type `{}`", text))
+			}
+		}
+	}
+}
+
+/// Returns the contents of `Wrapper<...>` when `text` has that exact shape.
+fn wrapped<'a>(text: &'a str, wrapper: &str) -> Option<&'a str> {
+	text.strip_prefix(wrapper)
+		.and_then(|rest| rest.strip_prefix('<'))
+		.and_then(|rest| rest.strip_suffix('>'))
+}
@@ -0,0 +1,13 @@
+//! A second, independent tokenizer/parser pair for the `.vtc` grammar.
+//!
+//! This predates [`parser`](crate::parser) and scans its own token set
+//! (`token::TokenKind`) rather than going through `nom`; [`parser::RParser`]
+//! turns that token stream into the same [`crate::value::VtcFile`] AST the
+//! rest of the crate uses, so the two front-ends agree on the result even
+//! though neither shares code with the other. Prefer `crate::parser::parse_vtc`
+//! for new code — this module exists for callers that specifically want the
+//! hand-rolled scanner's diagnostics.
+
+pub mod parser;
+pub mod token;
+pub mod types;
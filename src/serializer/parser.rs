@@ -1,15 +1,18 @@
-use std::ffi::c_void;
 use std::fmt;
-use std::fmt::Formatter;
-use std::process::id;
-use crate::serializer::token::LitKind;
-use crate::serializer::types::{Types, ValType};
-use crate::serializer::token::TokenKind::Literal;
-use crate::serializer::token::{TokenKind, Tokens};
-use crate::Stack;
+use std::sync::Arc;
 
+use smallvec::SmallVec;
+
+use crate::serializer::token::{Lit, LitKind, Span, TokenKind, Tokens};
+use crate::value::{Accessor, Namespace, Number, Reference, ReferenceType, Value, Variable, VtcFile};
+
+const SMALL_VEC_SIZE: usize = 4;
+
+/// A `%% key value` metadata line. Tags sit alongside containers in the
+/// source but are not part of the crate's [`VtcFile`] model, so they are
+/// collected separately rather than folded into a namespace.
 #[derive(Debug)]
-struct Tag {
+pub struct Tag {
 	pub t_value_1: String,
 	pub t_value_2: String,
 }
@@ -23,75 +26,33 @@ impl Tag {
 	}
 }
 
-#[derive(Debug)]
-struct Reference {
-	pub to_ref_value: String,
-	pub reference_range: Vec<u16>
-}
-
-#[derive(Debug)]
-struct Pointer {
-	pub pointing_container: String,
-	pub pointing_value: String,
-	pub reference_range: Vec<u16>
-}
-
-/// ListType stores the bits and segments of the value such as
-/// Reference, Pointer, or by Value
-/// * store_type: Type of value stored within
-/// * val_type: Type of value i.e., string, float, integer, ...
-/// * ref_to: An optional field that defines what this value references to, if applicable
-/// * points_to: An optional field that defines what this value points to, if applicable
-#[derive(Debug)]
-struct ListType {
-	pub store_type: ValType,
-	pub val_type: Types,
-	pub value: String,
-	pub ref_to: Option<Reference>,
-	pub points_to: Option<Pointer>,
-}
-
-/// Annotate and store type of value a PValue field may contain
-/// All values inside the field has to be a list
-#[derive(Debug)]
-enum VarType {
-	EmptyList(String),
-	List(Vec<ListType>)
-}
-
-/// This structure is internal to parser and should not be conflicted with
-/// the `container` structure found in container.rs
-/// * c_name: Name of the container
-/// * values: Intermediate representation of the values
-///     - First field is for name of the variable
-///     - Second field is for the contained value
-struct PContainer {
-	pub c_name: String,
-	pub values: Vec<(String, VarType)>
-}
-
-impl PContainer {
-	/// Initialize empty container
-	#[inline]
-	pub fn default() -> Self {
-		Self { c_name: String::new(), values: vec![] }
-	}
-
-	pub fn update_name(&mut self, name: &String) { self.c_name = name.clone(); }
+/// A positioned parse error. `span` is the byte range of the offending token
+/// (when one was available), `expected` lists what the parser was looking
+/// for, and `found` is the token it actually saw (`None` at end of input).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+	pub span: Option<Span>,
+	pub expected: Vec<String>,
+	pub found: Option<TokenKind>,
 }
 
-impl fmt::Debug for PContainer {
-	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-		f.debug_struct("PContainer")
-			.field("c_name", &self.c_name)
-			.field("values", &self.values)
-			.finish()
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "expected ")?;
+		if self.expected.is_empty() {
+			write!(f, "a different token")?;
+		} else {
+			write!(f, "{}", self.expected.join(" or "))?;
+		}
+		match &self.found {
+			Some(t) => write!(f, ", found {:?}", t),
+			None => write!(f, ", found end of input"),
+		}
 	}
 }
 
 pub struct RParser {
 	tag: Vec<Tag>,
-	p_container: Vec<PContainer>,
 	tokens: Tokens,
 	cursor: usize,
 }
@@ -99,129 +60,304 @@ pub struct RParser {
 impl RParser {
 	/// Constructs a new Root parser and populates with the tokens
 	pub fn new(tokens: Tokens) -> Self {
-		Self { tag: vec![], p_container: vec![], tokens, cursor: 0, }
-	}
-
-	/// Generate a simple-AST
-	pub fn generate_ast(&mut self) {
-		let total_token_count = self.tokens.tokens().len();
-		let tokens = self.tokens.tokens();
-
-		if tokens.is_empty() { return; }
-
-		let mut tags: Vec<Tag> = vec![];
-		loop {
-			let c_tok = tokens.iter().nth(self.cursor).unwrap();
-
-			// Throw an error if starting token is not DbPerc | At
-			let index = match c_tok {
-				TokenKind::DbPerc => {
-					let (tag, idx) = Self::parse_tags(&tokens, &self.cursor);
-					tags.push(tag);
-					idx
-				},
-				TokenKind::At => {
-					let (cont, idx) = Self::container_begin(&tokens, &self.cursor);
-					idx
-				},
-				TokenKind::Hash => (self.cursor + 1).try_into().unwrap(),
-				_ => -1,
-			};
-
-			if index <= 0 {
-				// TODO: raise error
-				println!("Encountered issue parsing: {:?}", c_tok);
-				break
-			}
-			self.cursor += index as usize;
+		Self { tag: vec![], tokens, cursor: 0 }
+	}
 
-			self.cursor += 1;
-			if self.cursor >= total_token_count { break }
+	/// The `%% key value` tags collected while parsing.
+	pub fn tags(&self) -> &[Tag] {
+		&self.tag
+	}
+
+	/// Parses the whole token stream into the crate's [`VtcFile`] AST:
+	/// `@name: $var := [ ... ]` containers become [`Namespace`]s, `%%`
+	/// lines are collected as [`Tag`]s, and comments (`Hash`) are skipped.
+	pub fn parse(&mut self) -> Result<VtcFile, ParseError> {
+		let mut namespaces = Vec::new();
+		let total = self.tokens.tokens().len();
+
+		while self.cursor < total {
+			match self.peek() {
+				Some(TokenKind::DbPerc) => {
+					let tag = self.parse_tag()?;
+					self.tag.push(tag);
+				}
+				Some(TokenKind::At) => {
+					namespaces.push(self.parse_container()?);
+				}
+				Some(TokenKind::Hash) => {
+					self.cursor += 1;
+				}
+				_ => return Err(self.error(&["'%%'", "'@'", "'#'"])),
+			}
 		}
+
+		Ok(VtcFile { namespaces })
 	}
 
-	/// Peek through the next token value
-	#[inline]
-	fn peek<'a>(tokens: &'a Vec<TokenKind>, c_idx: &'a usize) -> &'a TokenKind {
-		let n_tok = tokens.iter().nth(c_idx + 1);
-		match n_tok {
-			Some(v) => v,
-			None => &TokenKind::Blank
+	/// Backwards-compatible entry point: runs [`RParser::parse`] and prints
+	/// any error rather than returning it.
+	pub fn generate_ast(&mut self) -> Option<VtcFile> {
+		match self.parse() {
+			Ok(file) => Some(file),
+			Err(e) => {
+				println!("Encountered issue parsing: {}", e);
+				None
+			}
 		}
 	}
 
-	/// Parse container:
-	/// @container: ...
-	/// Grammar: <@> + <String> + <:>
-	///     + <$> + <String> + <:=> + <[> + <ListValue> + <]>
-	#[inline]
-	fn container_begin(tokens: &Vec<TokenKind>, c_idx: &usize) -> (PContainer, i32) {
-		// We know that current index points to TokenKind::At
-		let mut w_idx = c_idx + 1;
-		let t_size = tokens.len();
-		// Return error if index at next token == total size of token_kind
-		if w_idx >= t_size { return (PContainer::default(), -1) }
-		let mut t_container = PContainer::default();
-
-		// Extract container name:
-		let pk = Self::peek(&tokens, &w_idx);
-		let container_name = match pk {
-			TokenKind::Literal(v) => v.value.clone(),
-			_ => String::new(),
-		};
-		println!("Container name: {}", container_name);
-		if container_name.is_empty() { return (t_container, -1) }
-		t_container.c_name = container_name;
+	// ---- token-stream helpers --------------------------------------------
+
+	fn peek(&self) -> Option<&TokenKind> {
+		self.tokens.tokens().get(self.cursor)
+	}
+
+	fn peek_at(&self, offset: usize) -> Option<&TokenKind> {
+		self.tokens.tokens().get(self.cursor + offset)
+	}
+
+	fn advance(&mut self) -> Option<&TokenKind> {
+		let tok = self.tokens.tokens().get(self.cursor);
+		if tok.is_some() {
+			self.cursor += 1;
+		}
+		tok
+	}
 
-		w_idx += 1;
-		loop {
-			let w_tok = tokens.iter().nth(w_idx).unwrap();
-			match w_tok {
-				_ => {}
-			};
+	fn error(&self, expected: &[&str]) -> ParseError {
+		ParseError {
+			span: self.tokens.span(self.cursor),
+			expected: expected.iter().map(|s| s.to_string()).collect(),
+			found: self.peek().cloned(),
+		}
+	}
 
-			w_idx += 1;
-			if w_idx >= t_size { break }
+	fn expect(&mut self, expected: &TokenKind, label: &str) -> Result<(), ParseError> {
+		match self.peek() {
+			Some(t) if t == expected => {
+				self.cursor += 1;
+				Ok(())
+			}
+			_ => Err(self.error(&[label])),
 		}
+	}
 
-		println!("{:#?}", t_container);
-		(t_container, w_idx as i32)
+	/// Consumes a `Literal` token of any kind and returns its raw text.
+	fn expect_literal(&mut self, label: &str) -> Result<Lit, ParseError> {
+		match self.peek().cloned() {
+			Some(TokenKind::Literal(lit)) => {
+				self.cursor += 1;
+				Ok(lit)
+			}
+			_ => Err(self.error(&[label])),
+		}
 	}
 
+	// ---- tags ---------------------------------------------------------------
+
 	/// Parse tags:
 	/// %% foo bar ...
 	/// Grammar: <%%> + <String> + <String>
-	#[inline]
-	fn parse_tags(tokens: &Vec<TokenKind>, c_idx: &usize) -> (Tag, i32) {
-		let w_idx = c_idx + 1;
-		let in_range = c_idx + 2 <= tokens.len();
-
-		// Early error
-		if !in_range { return (Tag::default(), -1) }
-
-		let mut lit_count = 0;
-		let mut tag_values = Vec::new();
-		loop {
-			let w_tok = tokens.iter().nth(w_idx).unwrap();
-			let value = match w_tok {
-				Literal(v) => v.value.clone(),
-				_ => "".to_string(),
-			};
-			// Failed first check
-			if value.is_empty() { return(Tag::default(), -1) }
-			println!("Tag_x :: {}", value);
-			tag_values.push(value);
-
-			lit_count += 1;
-			if lit_count == 2 { break }
-		}
-		assert_eq!(tag_values.len(), 2);
-
-		let tag = Tag {
-			t_value_1: tag_values.iter().nth(0).unwrap().clone(),
-			t_value_2: tag_values.iter().nth(1).unwrap().clone(),
+	fn parse_tag(&mut self) -> Result<Tag, ParseError> {
+		self.expect(&TokenKind::DbPerc, "'%%'")?;
+		let t_value_1 = self.expect_literal("a tag key")?.value;
+		let t_value_2 = self.expect_literal("a tag value")?.value;
+		Ok(Tag { t_value_1, t_value_2 })
+	}
+
+	// ---- containers -----------------------------------------------------
+
+	/// Parse container:
+	/// @container: $var := [ ... ] ...
+	/// Grammar: <@> + <String> + <:> + (<$> + <String> + <:=> + <value>)*
+	fn parse_container(&mut self) -> Result<Namespace, ParseError> {
+		self.expect(&TokenKind::At, "'@'")?;
+		let name = self.expect_literal("a container name")?.value;
+		self.expect(&TokenKind::Col, "':'")?;
+
+		let mut variables = Vec::new();
+		while matches!(self.peek(), Some(TokenKind::Doll)) {
+			variables.push(self.parse_variable()?);
+		}
+
+		Ok(Namespace { name, variables })
+	}
+
+	/// Parse variable:
+	/// $var := <value>
+	fn parse_variable(&mut self) -> Result<Variable, ParseError> {
+		self.expect(&TokenKind::Doll, "'$'")?;
+		let name = self.expect_literal("a variable name")?.value;
+		self.expect(&TokenKind::ColEq, "':='")?;
+		let value = self.parse_value()?;
+		Ok(Variable { name, value })
+	}
+
+	// ---- values -----------------------------------------------------------
+
+	fn parse_value(&mut self) -> Result<Value, ParseError> {
+		match self.peek() {
+			Some(TokenKind::LBrack) => self.parse_list(),
+			Some(TokenKind::Perc) => self.parse_reference(ReferenceType::Local),
+			Some(TokenKind::Amp) => self.parse_reference(ReferenceType::External),
+			Some(TokenKind::Literal(_)) => self.parse_literal_or_pointer(),
+			_ => Err(self.error(&["a value"])),
+		}
+	}
+
+	fn parse_list(&mut self) -> Result<Value, ParseError> {
+		self.expect(&TokenKind::LBrack, "'['")?;
+		let mut values = Vec::new();
+		if !matches!(self.peek(), Some(TokenKind::RBrack)) {
+			loop {
+				values.push(self.parse_value()?);
+				match self.peek() {
+					Some(TokenKind::Comma) => {
+						self.cursor += 1;
+					}
+					_ => break,
+				}
+			}
+		}
+		self.expect(&TokenKind::RBrack, "']'")?;
+		Ok(Value::List(Arc::new(values)))
+	}
+
+	/// A bare `Literal` either stands for a scalar (string/number/bool/nil)
+	/// or, when immediately followed by `->`, names a pointer into another
+	/// container: `container->value`, equivalent to a local reference but
+	/// spelled with an arrow instead of a dot.
+	fn parse_literal_or_pointer(&mut self) -> Result<Value, ParseError> {
+		let lit = self.expect_literal("a value")?;
+		if lit.kind == LitKind::String && matches!(self.peek(), Some(TokenKind::DashGT)) {
+			self.cursor += 1;
+			let variable = self.expect_literal("a pointer target")?.value;
+			let accessors = self.parse_accessors()?;
+			return Ok(Value::Reference(Reference {
+				ref_type: ReferenceType::Local,
+				namespace: Some(Arc::new(lit.value)),
+				variable: Arc::new(variable),
+				accessors,
+			}));
+		}
+		Ok(literal_to_value(&lit))
+	}
+
+	/// `%ns.var` / `&ns.var` (the namespace is optional: `%var` resolves in
+	/// the enclosing namespace), followed by zero or more accessor steps.
+	fn parse_reference(&mut self, ref_type: ReferenceType) -> Result<Value, ParseError> {
+		match ref_type {
+			ReferenceType::Local => self.expect(&TokenKind::Perc, "'%'")?,
+			ReferenceType::External => self.expect(&TokenKind::Amp, "'&'")?,
+		}
+
+		let first = self.expect_literal("a reference name")?.value;
+		let (namespace, variable) = if matches!(self.peek(), Some(TokenKind::Dot)) {
+			self.cursor += 1;
+			let var = self.expect_literal("a reference variable")?.value;
+			(Some(Arc::new(first)), Arc::new(var))
+		} else {
+			(None, Arc::new(first))
 		};
 
-		(tag, (w_idx + 2) as i32)
+		let accessors = self.parse_accessors()?;
+		Ok(Value::Reference(Reference { ref_type, namespace, variable, accessors }))
+	}
+
+	/// Zero or more `->(i)`, `->(a..b)`, `->[key]` accessor steps.
+	fn parse_accessors(&mut self) -> Result<SmallVec<[Accessor; SMALL_VEC_SIZE]>, ParseError> {
+		let mut accessors = SmallVec::new();
+		while matches!(self.peek(), Some(TokenKind::DashGT)) {
+			self.cursor += 1;
+			accessors.push(self.parse_accessor()?);
+		}
+		Ok(accessors)
+	}
+
+	fn parse_accessor(&mut self) -> Result<Accessor, ParseError> {
+		match self.peek() {
+			Some(TokenKind::LParen) => self.parse_index_or_range(),
+			Some(TokenKind::LBrack) => self.parse_key(),
+			_ => Err(self.error(&["'(' or '['"])),
+		}
+	}
+
+	fn parse_index_or_range(&mut self) -> Result<Accessor, ParseError> {
+		self.expect(&TokenKind::LParen, "'('")?;
+
+		// Open start: `(..)` / `(..end)`.
+		if matches!(self.peek(), Some(TokenKind::DbDot)) {
+			self.cursor += 1;
+			if matches!(self.peek(), Some(TokenKind::RParen)) {
+				self.cursor += 1;
+				return Ok(Accessor::RangeFull);
+			}
+			let end = self.parse_index_literal()?;
+			self.expect(&TokenKind::RParen, "')'")?;
+			return Ok(Accessor::RangeTo(end));
+		}
+
+		let start = self.parse_index_literal()?;
+		if matches!(self.peek(), Some(TokenKind::DbDot)) {
+			self.cursor += 1;
+			// `(start..)` leaves the end open.
+			if matches!(self.peek(), Some(TokenKind::RParen)) {
+				self.cursor += 1;
+				return Ok(Accessor::RangeFrom(start));
+			}
+			let end = self.parse_index_literal()?;
+			self.expect(&TokenKind::RParen, "')'")?;
+			return Ok(Accessor::Range(start, end));
+		}
+
+		self.expect(&TokenKind::RParen, "')'")?;
+		Ok(Accessor::Index(start))
+	}
+
+	fn parse_index_literal(&mut self) -> Result<usize, ParseError> {
+		let lit = self.expect_literal("an index")?;
+		if lit.kind != LitKind::Int {
+			return Err(self.error(&["an integer index"]));
+		}
+		lit.value.parse::<usize>().map_err(|_| self.error(&["an integer index"]))
+	}
+
+	fn parse_key(&mut self) -> Result<Accessor, ParseError> {
+		self.expect(&TokenKind::LBrack, "'['")?;
+		let key = self.expect_literal("a key")?.value;
+		self.expect(&TokenKind::RBrack, "']'")?;
+		Ok(Accessor::Key(key))
+	}
+}
+
+/// Converts a scanned [`Lit`] into its [`Value`], recognizing the `true` /
+/// `false` / `nil` / `null` keywords (the tokenizer has no dedicated
+/// boolean/null token kind, so every bare word comes through as `LitKind::String`).
+fn literal_to_value(lit: &Lit) -> Value {
+	match lit.kind {
+		LitKind::Int => Value::Number(parse_int_literal(&lit.value)),
+		LitKind::Float => Value::Number(Number::Float(lit.value.parse().unwrap_or(0.0))),
+		LitKind::Bin => Value::Number(Number::Binary(i64::from_str_radix(&lit.value[2..], 2).unwrap_or(0))),
+		LitKind::Hex => Value::Number(Number::Hexadecimal(i64::from_str_radix(&lit.value[2..], 16).unwrap_or(0))),
+		LitKind::Bool => Value::Boolean(lit.value == "true"),
+		LitKind::Null => Value::Nil,
+		LitKind::String => match lit.value.as_str() {
+			"true" => Value::Boolean(true),
+			"false" => Value::Boolean(false),
+			"nil" | "null" => Value::Nil,
+			_ => Value::String(lit.value.clone()),
+		},
+	}
+}
+
+/// Parses a decimal integer literal, promoting to [`Number::BigInt`] when it
+/// overflows `i64` — mirroring `parser::lexer`'s handling of oversized integers.
+fn parse_int_literal(raw: &str) -> Number {
+	match raw.parse::<i64>() {
+		Ok(i) => Number::Integer(i),
+		Err(_) => match crate::bignum::BigInt::from_decimal(raw) {
+			Some(b) => Number::from_bigint(b),
+			None => Number::Integer(0),
+		},
 	}
 }
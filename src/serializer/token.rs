@@ -3,23 +3,140 @@ use std::fs::File;
 use std::fmt::Formatter;
 use std::io::{Error, Read};
 
-#[inline]
-fn str_peek(buff: &String, c_idx: &usize) -> char {
-	let next = match buff.chars().nth(c_idx + 1) {
-		Some(value) => value,
-		None => '\0'
-	};
-	next
+/// A half-open byte range `[start, end)` into the source, attached to every
+/// emitted token so downstream code and error blocks can point at exact source
+/// locations without re-scanning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+	pub start: usize,
+	pub end: usize,
 }
 
-#[inline]
-fn check_for_special_chars(c: char) -> bool {
-	let check = match c {
-		':' | '-' | ',' | ']' | '[' | ')'
-		| '(' | '{' | '}' | '.' => true,
-		_   => false,
-	};
-	check
+impl Span {
+	#[inline]
+	pub fn new(start: usize, end: usize) -> Self {
+		Self { start, end }
+	}
+}
+
+/// A single lexer error. Diagnostics are accumulated across the whole file so a
+/// user sees every problem in one pass, and carry the byte [`Span`] plus the
+/// derived `(line, column)` so the caret view can be rendered without
+/// rescanning the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+	pub message: String,
+	pub span: Span,
+	pub line: usize,
+	pub column: usize,
+}
+
+impl Diagnostic {
+	/// Builds a diagnostic for `span`, deriving its line/column from `source`.
+	fn new(message: String, span: Span, source: &str) -> Self {
+		let (line, column) = line_col(source, span.start);
+		Self { message, span, line, column }
+	}
+
+	/// Renders a caret-underlined view of the offending source line. The caret
+	/// row is drawn from the stored span, so no rescanning is needed.
+	pub fn render(&self, source: &str) -> String {
+		let line_start = source[..self.span.start].rfind('\n').map_or(0, |i| i + 1);
+		let line_end = source[self.span.start..]
+			.find('\n')
+			.map_or(source.len(), |i| self.span.start + i);
+		let line_text = &source[line_start..line_end];
+		let caret_col = self.span.start - line_start;
+		let width = self.span.end.saturating_sub(self.span.start).max(1);
+
+		let mut out = format!("error: {} [{}:{}]\n", self.message, self.line, self.column);
+		out.push_str(line_text);
+		out.push('\n');
+		out.push_str(&" ".repeat(caret_col));
+		out.push('^');
+		out.push_str(&"~".repeat(width - 1));
+		out
+	}
+
+	/// Renders several diagnostics against a shared source, blank-line separated.
+	pub fn render_all(diagnostics: &[Diagnostic], source: &str) -> String {
+		diagnostics
+			.iter()
+			.map(|d| d.render(source))
+			.collect::<Vec<_>>()
+			.join("\n\n")
+	}
+}
+
+/// Maps a byte offset to a 1-based `(line, column)` by scanning from the start.
+/// Called only when building a diagnostic, so the linear cost is paid on the
+/// error path only.
+fn line_col(source: &str, byte: usize) -> (usize, usize) {
+	let mut line = 1;
+	let mut col = 1;
+	for (i, ch) in source.char_indices() {
+		if i >= byte {
+			break;
+		}
+		if ch == '\n' {
+			line += 1;
+			col = 1;
+		} else {
+			col += 1;
+		}
+	}
+	(line, col)
+}
+
+/// A decoded scanning cursor: the source decoded once into `chars` with a
+/// parallel `offsets` table mapping each char index to its byte offset
+/// (`offsets[chars.len()]` is the total byte length). Indexing either vector is
+/// O(1), so the whole tokenizer runs in linear time instead of re-walking the
+/// string with `chars().nth(i)` on every access.
+struct Cursor {
+	chars: Vec<char>,
+	offsets: Vec<usize>,
+}
+
+impl Cursor {
+	fn new(data: &str) -> Self {
+		let mut chars = Vec::with_capacity(data.len());
+		let mut offsets = Vec::with_capacity(data.len() + 1);
+		for (byte, ch) in data.char_indices() {
+			chars.push(ch);
+			offsets.push(byte);
+		}
+		offsets.push(data.len());
+		Self { chars, offsets }
+	}
+
+	#[inline]
+	fn len(&self) -> usize {
+		self.chars.len()
+	}
+
+	/// The char at `idx`, or `'\0'` past the end.
+	#[inline]
+	fn at(&self, idx: usize) -> char {
+		self.chars.get(idx).copied().unwrap_or('\0')
+	}
+
+	/// The char after `idx`, or `'\0'` past the end.
+	#[inline]
+	fn peek(&self, idx: usize) -> char {
+		self.chars.get(idx + 1).copied().unwrap_or('\0')
+	}
+
+	/// Byte offset of char index `idx`.
+	#[inline]
+	fn byte(&self, idx: usize) -> usize {
+		self.offsets[idx.min(self.chars.len())]
+	}
+
+	/// Slices the source text spanned by char indices `[start, end)`.
+	fn slice(&self, start: usize, end: usize) -> String {
+		self.chars[start..end.min(self.chars.len())].iter().collect()
+	}
 }
 
 /*
@@ -146,6 +263,7 @@ impl fmt::Debug for TokenKind {
 pub struct Tokens {
 	file_data:  String,
 	tokens:     Vec<TokenKind>,
+	spans:      Vec<Span>,
 	data:       Vec<String>
 }
 
@@ -160,8 +278,9 @@ impl Tokens {
 		file.read_to_string(&mut file_data)?;
 
 		let tokens = vec![];
+		let spans = vec![];
 		let data = vec![];
-		Ok(Self { file_data, tokens, data })
+		Ok(Self { file_data, tokens, spans, data })
 	}
 
 	/// Returns total size of tokens
@@ -174,54 +293,52 @@ impl Tokens {
 		&self.tokens
 	}
 
-	pub fn tokenize(&mut self) -> Result<(), Error>{
-		let len = self.file_data.len();
-		let data = self.file_data.clone();
+	/// The byte-range spans, one per emitted token and positionally aligned with
+	/// [`Tokens::tokens`].
+	pub fn spans(&self) -> &Vec<Span> {
+		&self.spans
+	}
+
+	/// The span of the token at `index`, if any.
+	pub fn span(&self, index: usize) -> Option<Span> {
+		self.spans.get(index).copied()
+	}
 
-		let mut err = false;
+	/// Tokenizes the file. Errors do not abort: each invalid token records a
+	/// [`Diagnostic`], the scanner recovers to the next whitespace boundary and
+	/// keeps going, so a single pass reports every problem. Returns the full
+	/// diagnostic list when anything went wrong.
+	pub fn tokenize(&mut self) -> Result<(), Vec<Diagnostic>> {
+		let cursor = Cursor::new(&self.file_data);
+		let len = cursor.len();
+
+		let mut diagnostics: Vec<Diagnostic> = Vec::new();
 		let mut idx = 0;
-		while idx != len {
-			let cchar = data.chars().nth(idx).unwrap_or('\0');
+		while idx < len {
+			let start = idx;
+			let cchar = cursor.at(idx);
 			let value = match cchar {
 				//// Colon[':']
 				':' => {
-					let nchar = str_peek(&data, &idx);
-					let p_col = if nchar == '=' {
+					if cursor.peek(idx) == '=' {
 						idx += 1;
 						TokenKind::ColEq
 					} else {
 						TokenKind::Col
-					};
-					p_col
+					}
 				},
 				'-' => {
-					let nchar = str_peek(&data, &idx);
-					let a_col = if nchar == '>' {
+					if cursor.peek(idx) == '>' {
 						idx += 1;
 						TokenKind::DashGT
 					} else {
-						err = true;
-						let err_block = Self::generate_err_block(&data, &idx, " Invalid token character. Perhaps you meant '->'");
-						TokenKind::Err(TokErr::new(err_block))
-					};
-					a_col
+						TokenKind::Err(TokErr::new("invalid token character, perhaps you meant '->'".to_string()))
+					}
 				}
 				//// N-Blocks
-				'@' => {
-					let (value, index) = Self::process_n_block_chars(&data, &idx, TokenKind::At);
-					idx = index;
-					value
-				},
-				'&' => {
-					let (value, index) = Self::process_n_block_chars(&data, &idx, TokenKind::Amp);
-					idx = index;
-					value
-				}
-				'$' => {
-					let (value, index) = Self::process_n_block_chars(&data, &idx, TokenKind::Doll);
-					idx = index;
-					value
-				},
+				'@' => Self::process_n_block_chars(&cursor, idx, TokenKind::At),
+				'&' => Self::process_n_block_chars(&cursor, idx, TokenKind::Amp),
+				'$' => Self::process_n_block_chars(&cursor, idx, TokenKind::Doll),
 				//// Brackets
 				'[' => TokenKind::LBrack,
 				']' => TokenKind::RBrack,
@@ -235,170 +352,207 @@ impl Tokens {
 				',' => TokenKind::Comma,
 				//// Dots
 				'.' => {
-					let mut c_idx = idx.clone();
-					while data.chars().nth(c_idx).unwrap() == '.' { c_idx += 1 }
+					let mut c_idx = idx;
+					while cursor.at(c_idx) == '.' { c_idx += 1 }
 
 					let count = c_idx - idx;
 					let dot_count = match count {
 						1 => TokenKind::Dot,
 						2 => TokenKind::DbDot,
 						3 => TokenKind::TripDot,
-						_ => {
-							err = true;
-							let err_msg = Self::generate_err_block(&data, &idx, " Invalid token character. Perhaps you meant to use one of these [., .., ...]");
-							TokenKind::Err(TokErr::new(err_msg))
-						},
+						_ => TokenKind::Err(TokErr::new("invalid token, use one of [., .., ...]".to_string())),
 					};
 					idx += count - 1;
 					dot_count
 				}
 				//// %, %%
 				'%' => {
-					let nchar = str_peek(&data, &idx);
-					let mut token_is: TokenKind = TokenKind::Blank;
+					let nchar = cursor.peek(idx);
 					match nchar {
-						'%' => { idx += 1;  token_is = TokenKind::DbPerc; },
-						'a'..='z' | 'A'..='Z' | '_'   => { token_is = TokenKind::Perc; }
+						'%' => { idx += 1; TokenKind::DbPerc },
+						'a'..='z' | 'A'..='Z' | '_' => TokenKind::Perc,
 						// Pointer to a numerical is prohibited
-						' ' | '0'..='9' => {
-							err = true;
-							let err_msg = Self::generate_err_block(&data, &idx, " Encountered illegal token after '%'");
-							token_is = TokenKind::Err(TokErr::new(err_msg));
-						}
-						_ => {}
-					};
-					token_is
+						' ' | '0'..='9' => TokenKind::Err(TokErr::new("encountered illegal token after '%'".to_string())),
+						_ => TokenKind::Blank,
+					}
 				}
 				//// Comment block
 				'#' => {
-					let (skip_to, comment_block) = Self::parse_comment_block(&data, idx.clone());
+					let (skip_to, comment_block) = Self::parse_comment_block(&cursor, idx);
 					self.data.push(comment_block);
 					idx = skip_to;
 					TokenKind::Hash
 				}
 				//// Assignment Error
-				'=' => {
-					err = true;
-					let err_block = Self::generate_err_block(&data, &idx, " Use ':=' for assignment operations");
-					TokenKind::Err(TokErr::new(err_block))
-				}
+				'=' => TokenKind::Err(TokErr::new("use ':=' for assignment operations".to_string())),
 				//// AlphaNumeric + Misc
 				_ => {
-					let (token, index) = Self::process_alpha_numeric_misc(&data, &idx);
+					let (token, index) = Self::process_alpha_numeric_misc(&cursor, idx);
 					idx = index;
 					token
 				},
 			};
 
+			let span = Span::new(cursor.byte(start), cursor.byte(idx + 1));
+
+			if let TokenKind::Err(e) = &value {
+				diagnostics.push(Diagnostic::new(e.msg.clone(), span, &self.file_data));
+				// Recovery: drop the offending run up to the next whitespace
+				// boundary and resume scanning so later errors are still found.
+				// `idx` may already sit on that boundary (e.g. after `0x `).
+				while idx < len && !cursor.at(idx).is_whitespace() { idx += 1; }
+				continue;
+			}
+
 			idx += 1;
 			if value == TokenKind::EOF { break }
-			if err {
-				let err_msg = match value.clone() {
-					TokenKind::Err(e) => e.msg,
-					_ => TokErr::default().msg
-				};
-				eprintln!("{}", err_msg);
-				break
-				// One way to deal with this error is to stack-dump (?)
-				// TODO: Figure out the fastest way to do this ...
-			}
 			self.tokens.push(value);
+			self.spans.push(span);
+		}
+
+		// Cleanup: drop the placeholder Blank tokens, keeping spans aligned.
+		let mut kept = 0;
+		for i in 0..self.tokens.len() {
+			if self.tokens[i] != TokenKind::Blank {
+				self.tokens.swap(kept, i);
+				self.spans.swap(kept, i);
+				kept += 1;
+			}
 		}
+		self.tokens.truncate(kept);
+		self.spans.truncate(kept);
 
-		// Cleanup
-		if !err { self.tokens.retain(|r| *r != TokenKind::Blank); }
-		Ok(())
+		if diagnostics.is_empty() {
+			Ok(())
+		} else {
+			Err(diagnostics)
+		}
 	}
 
 	///
 	/// Parse comment block
 	///
 	#[inline]
-	fn parse_comment_block(data: &String, mut idx: usize) -> (usize, String) {
-		let c_idx = idx.clone();
-		while data.chars().nth(idx).unwrap() != '\n' { idx += 1; }
-		let skip_by = idx - c_idx;
-		let comment_block: String = data.chars().skip(c_idx).take(skip_by).collect();
+	fn parse_comment_block(cursor: &Cursor, mut idx: usize) -> (usize, String) {
+		let c_idx = idx;
+		while idx < cursor.len() && cursor.at(idx) != '\n' { idx += 1; }
+		let comment_block = cursor.slice(c_idx, idx);
 		(idx, comment_block)
 	}
 
 	///
-	/// Create 'fancy' error block
+	/// Classify a literal starting at `idx`: a digit run dispatches to the
+	/// numeric scanner (`Hex`/`Bin`/`Float`/`Int`), anything else is scanned as
+	/// an identifier and kept as `String`. Returns the index of the last char
+	/// consumed so the caller resumes from there.
 	///
 	#[inline]
-	fn generate_err_block(data: &String, idx: &usize, msg: &str) -> String {
-		let c_idx = idx.clone();
-		let mut w_idx = idx.clone();
-		while data.chars().nth(w_idx).unwrap() != '\n' {
-			w_idx += 1;
+	fn process_alpha_numeric_misc(cursor: &Cursor, idx: usize) -> (TokenKind, usize) {
+		if cursor.at(idx).is_ascii_digit() {
+			return Self::process_numeric(cursor, idx);
 		}
-		let mut error: String = data.chars().skip(*idx).take(w_idx - c_idx).collect();
-		error.push_str("\n^~~~");
-		error.push_str(msg);
-		error
+
+		let mut c_idx = idx;
+		let mut value: String = String::new();
+		while c_idx < cursor.len() {
+			let c_value = cursor.at(c_idx);
+			if matches!(c_value, 'a'..='z' | 'A'..='Z' | '_' | '0'..='9') {
+				value.push(c_value);
+				c_idx += 1;
+			} else {
+				break;
+			}
+		}
+
+		// Return early
+		if value.is_empty() { return (TokenKind::Blank, idx) }
+
+		let token = TokenKind::Literal(Lit::new(LitKind::String, value));
+		(token, c_idx - 1)
 	}
 
 	///
-	/// Filter out alphanumeric values
-	/// TODO: Return error on failure
+	/// Scan a numeric literal. `0x`/`0X` and `0b`/`0B` dispatch to the radix
+	/// scanner; otherwise a decimal run with an optional single `.` fraction and
+	/// `e`/`E` exponent is `Float`, and a bare digit run is `Int`. Malformed
+	/// forms (`0x`, `0b2`, `1.2.3`, digits glued to identifier chars) produce a
+	/// token error rather than a silent `String`.
 	///
 	#[inline]
-	fn process_alpha_numeric_misc(data: &String, idx: &usize) -> (TokenKind, usize) {
-		let mut c_idx = idx.clone();
-		let mut token = TokenKind::EOF;
+	fn process_numeric(cursor: &Cursor, idx: usize) -> (TokenKind, usize) {
+		if cursor.at(idx) == '0' {
+			match cursor.peek(idx) {
+				'x' | 'X' => return Self::scan_radix(cursor, idx, 16, LitKind::Hex, "malformed hexadecimal literal"),
+				'b' | 'B' => return Self::scan_radix(cursor, idx, 2, LitKind::Bin, "malformed binary literal"),
+				_ => {}
+			}
+		}
 
-		let failure = false;
-		let mut value: String = String::new();
-		let err: TokErr = TokErr { msg: "".to_string() };
-
-		loop {
-			let c_value = data.chars().nth(c_idx).unwrap();
-			// TODO: Logic fix
-			// Current logic increments the index counter raising issues in the program.
-			// If it encounters these characters then it tries to decrement the counter by one
-			if check_for_special_chars(c_value) { c_idx -= 1; }
-			let is_valid = match c_value {
-				'a'..='z' | 'A'..='Z' | '_' | '0'..='9' => true,
-				_ => false,
-			};
+		let mut c_idx = idx;
+		let mut is_float = false;
+		while cursor.at(c_idx).is_ascii_digit() { c_idx += 1; }
 
-			if is_valid {
-				c_idx += 1;
-				value.push(c_value);
-			} else { break; }
+		// A fraction is only a fraction when a digit follows the dot; `1..5`
+		// stops here so the `..` range token is left intact.
+		if cursor.at(c_idx) == '.' && cursor.at(c_idx + 1).is_ascii_digit() {
+			is_float = true;
+			c_idx += 1;
+			while cursor.at(c_idx).is_ascii_digit() { c_idx += 1; }
 		}
 
-		// Return early
-		if failure { return (TokenKind::Err(err), c_idx) }
-		if value.is_empty() { return (TokenKind::Blank, c_idx) }
-
-		if value.chars().all(char::is_alphanumeric) || !value.is_empty() {
-			// Integer Check
-			let lit_check = match value.parse::<i64>() {
-				Ok(_) => LitKind::Float,
-				Err(_) => LitKind::String
-			};
-			let lit_kind = Lit::new(lit_check, value);
-			token = TokenKind::Literal(lit_kind);
+		// Optional exponent, accepted only when followed by an optional sign and
+		// at least one digit.
+		if matches!(cursor.at(c_idx), 'e' | 'E') {
+			let mut j = c_idx + 1;
+			if matches!(cursor.at(j), '+' | '-') { j += 1; }
+			if cursor.at(j).is_ascii_digit() {
+				is_float = true;
+				c_idx = j;
+				while cursor.at(c_idx).is_ascii_digit() { c_idx += 1; }
+			}
+		}
+
+		// Reject a second decimal point (`1.2.3`) or identifier chars glued to
+		// the number (`12abc`) — neither is a well-formed literal.
+		let trailing = cursor.at(c_idx);
+		let malformed = (trailing == '.' && cursor.at(c_idx + 1).is_ascii_digit())
+			|| matches!(trailing, 'a'..='z' | 'A'..='Z' | '_');
+		if malformed {
+			return (TokenKind::Err(TokErr::new("malformed numeric literal".to_string())), c_idx);
 		}
-		(token, c_idx)
+
+		let kind = if is_float { LitKind::Float } else { LitKind::Int };
+		let value = cursor.slice(idx, c_idx);
+		(TokenKind::Literal(Lit::new(kind, value)), c_idx - 1)
+	}
+
+	///
+	/// Scan a radix-prefixed literal (`0x…`/`0b…`). Requires at least one digit
+	/// in the given radix and no identifier chars glued to the end.
+	///
+	#[inline]
+	fn scan_radix(cursor: &Cursor, idx: usize, radix: u32, kind: LitKind, err_msg: &str) -> (TokenKind, usize) {
+		let start = idx + 2;
+		let mut c_idx = start;
+		while cursor.at(c_idx).is_digit(radix) { c_idx += 1; }
+
+		let glued = matches!(cursor.at(c_idx), 'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '.');
+		if c_idx == start || glued {
+			return (TokenKind::Err(TokErr::new(err_msg.to_string())), c_idx.max(idx + 1));
+		}
+
+		let value = cursor.slice(idx, c_idx);
+		(TokenKind::Literal(Lit::new(kind, value)), c_idx - 1)
 	}
 
 	///
 	/// n block characters include @, &, and $
 	///
 	#[inline]
-	fn process_n_block_chars(data: &String, idx: &usize, r_type: TokenKind) -> (TokenKind, usize) {
-		let w_idx = idx.clone();
-		let nchar = str_peek(&data, &w_idx);
-
-		let retval = match nchar {
-			' ' => {
-				let err_block = Self::generate_err_block(&data, &w_idx, " Expects [a-zA-Z0-0] after '[@, &, $]'");
-				TokenKind::Err(TokErr::new(err_block))
-			},
-			_   => { r_type }
-		};
-		(retval, w_idx)
+	fn process_n_block_chars(cursor: &Cursor, idx: usize, r_type: TokenKind) -> TokenKind {
+		match cursor.peek(idx) {
+			' ' => TokenKind::Err(TokErr::new("expects [a-zA-Z0-9] after '@', '&' or '$'".to_string())),
+			_ => r_type,
+		}
 	}
 }
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use smallvec::SmallVec;
 
+use crate::bignum::BigInt;
 use crate::SMALL_VEC_SIZE;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -29,15 +30,108 @@ pub enum Value {
     Boolean(bool),
     Nil,
     List(Arc<Vec<Value>>),
+    /// An ordered map of string keys to values, addressed with `Accessor::Key`.
+    /// Insertion order is preserved so serialization and iteration are
+    /// deterministic, mirroring how [`Value::List`] keeps positional order.
+    Map(Arc<Vec<(Arc<String>, Value)>>),
     Reference(Reference),
+    Expr(Box<Expr>),
+}
+
+/// A computed value expression, evaluated lazily during reference resolution so
+/// that references embedded in the expression still participate in the normal
+/// resolution pass.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Unary { op: UnaryOp, operand: Value },
+    Binary { op: BinaryOp, lhs: Value, rhs: Value },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Concat,
+    Lt,
+    Gt,
+    Eq,
+}
+
+impl BinaryOp {
+    /// Left/right binding powers for precedence-climbing. `*` `/` bind tighter
+    /// than `+` `-` `++`, and comparisons bind loosest.
+    pub fn binding_power(self) -> (u8, u8) {
+        match self {
+            BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Eq => (1, 2),
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Concat => (3, 4),
+            BinaryOp::Mul | BinaryOp::Div => (5, 6),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum Number {
     Integer(i64),
     Float(f64),
     Binary(i64),
     Hexadecimal(i64),
+    /// An integer that does not fit in an `i64`. Produced by the lexer when a
+    /// decimal/hex literal overflows, and by the integer arithmetic intrinsics
+    /// when a result overflows; results that fit back into an `i64` are demoted
+    /// to [`Number::Integer`] so the common case stays cheap.
+    BigInt(BigInt),
+}
+
+impl Number {
+    /// Returns the `i64` value of any integer-flavoured variant, demoting an
+    /// in-range `BigInt`. `None` for floats and out-of-range big integers.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Number::Integer(i) | Number::Binary(i) | Number::Hexadecimal(i) => Some(*i),
+            Number::BigInt(b) => b.to_i64(),
+            Number::Float(_) => None,
+        }
+    }
+
+    /// Normalises a `BigInt` back to [`Number::Integer`] when it fits.
+    pub fn from_bigint(b: BigInt) -> Number {
+        match b.to_i64() {
+            Some(i) => Number::Integer(i),
+            None => Number::BigInt(b),
+        }
+    }
+
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Number::Integer(i) | Number::Binary(i) | Number::Hexadecimal(i) => Some(BigInt::from_i64(*i)),
+            Number::BigInt(b) => Some(b.clone()),
+            Number::Float(_) => None,
+        }
+    }
+}
+
+// Treat an in-range `BigInt` as equal to the `Integer`/`Binary`/`Hexadecimal`
+// holding the same value, so that adding the variant does not change how
+// existing integer values compare.
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Number::Float(a), Number::Float(b)) => a == b,
+            (Number::Float(_), _) | (_, Number::Float(_)) => false,
+            _ => match (self.to_bigint(), other.to_bigint()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -54,11 +148,24 @@ pub struct Reference {
     pub accessors: SmallVec<[Accessor; SMALL_VEC_SIZE]>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Accessor {
     Index(usize),
     Range(usize, usize),
     Key(String),
+    /// `(-n)` — the element `n` positions from the end, resolved against the
+    /// concrete list length at dereference time.
+    IndexFromEnd(usize),
+    /// `(start..)` — everything from `start` to the end.
+    RangeFrom(usize),
+    /// `(..end)` — everything up to (but excluding) `end`.
+    RangeTo(usize),
+    /// `(..)` — the whole list.
+    RangeFull,
+    /// `->?(..)` / `->?[key]` — optional navigation. If the wrapped accessor
+    /// fails (missing key, out-of-range index), resolution yields `Value::Nil`
+    /// instead of erroring, letting defaults flow naturally.
+    Optional(Box<Accessor>),
 }
 
 impl Value {
@@ -90,6 +197,13 @@ impl Value {
         }
     }
 
+    pub fn as_map(&self) -> Option<&[(Arc<String>, Value)]> {
+        match self {
+            Value::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
     pub fn as_reference(&self) -> Option<&Reference> {
         match self {
             Value::Reference(r) => Some(r),
@@ -0,0 +1,156 @@
+use tempfile::tempdir;
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use fnv::FnvHashMap;
+	use vtc::bignum::BigInt;
+	use vtc::runtime::binary::{decode_value, encode_value};
+	use vtc::runtime::Runtime;
+	use vtc::value::{
+		Accessor, BinaryOp, Expr, Number, Reference, ReferenceType, UnaryOp, Value,
+	};
+
+	use super::*;
+
+	/// Encodes `value`, decodes it back and asserts both the decoded value and a
+	/// re-encode of it match the originals byte-for-byte (canonical form).
+	fn assert_round_trip(value: Value) {
+		let mut encoded = Vec::new();
+		encode_value(&value, &mut encoded);
+
+		let (decoded, consumed) = decode_value(&encoded).expect("decode failed");
+		assert_eq!(consumed, encoded.len(), "decoder left trailing bytes");
+		assert_eq!(decoded, value, "decode(encode(v)) != v");
+
+		let mut re_encoded = Vec::new();
+		encode_value(&decoded, &mut re_encoded);
+		assert_eq!(re_encoded, encoded, "encoding is not canonical");
+	}
+
+	#[test]
+	fn round_trips_scalar_variants() {
+		assert_round_trip(Value::String("hello".to_string()));
+		assert_round_trip(Value::Intrinsic("std_add".to_string()));
+		assert_round_trip(Value::Boolean(true));
+		assert_round_trip(Value::Boolean(false));
+		assert_round_trip(Value::Nil);
+	}
+
+	#[test]
+	fn round_trips_number_sub_variants() {
+		assert_round_trip(Value::Number(Number::Integer(-42)));
+		assert_round_trip(Value::Number(Number::Integer(i64::MAX)));
+		assert_round_trip(Value::Number(Number::Binary(0b1011)));
+		assert_round_trip(Value::Number(Number::Hexadecimal(0xDEAD)));
+		assert_round_trip(Value::Number(Number::Float(3.141592653589793)));
+		assert_round_trip(Value::Number(Number::BigInt(
+			BigInt::from_decimal("170141183460469231731687303715884105727").unwrap(),
+		)));
+	}
+
+	#[test]
+	fn radix_tag_survives_round_trip() {
+		// The same numeric value under different radix tags must stay distinct
+		// in the encoded bytes.
+		let mut bin = Vec::new();
+		encode_value(&Value::Number(Number::Binary(255)), &mut bin);
+		let mut hex = Vec::new();
+		encode_value(&Value::Number(Number::Hexadecimal(255)), &mut hex);
+		assert_ne!(bin, hex);
+	}
+
+	#[test]
+	fn round_trips_list_and_map() {
+		assert_round_trip(Value::List(Arc::new(vec![
+			Value::Number(Number::Integer(1)),
+			Value::String("two".to_string()),
+			Value::Nil,
+		])));
+		assert_round_trip(Value::Map(Arc::new(vec![
+			(Arc::new("a".to_string()), Value::Boolean(true)),
+			(
+				Arc::new("b".to_string()),
+				Value::List(Arc::new(vec![Value::Number(Number::Integer(9))])),
+			),
+		])));
+	}
+
+	#[test]
+	fn round_trips_reference_with_accessors() {
+		assert_round_trip(Value::Reference(Reference {
+			ref_type: ReferenceType::External,
+			namespace: Some(Arc::new("app".to_string())),
+			variable: Arc::new("server".to_string()),
+			accessors: smallvec_accessors(vec![
+				Accessor::Key("primary".to_string()),
+				Accessor::Index(0),
+				Accessor::Range(1, 3),
+				Accessor::Optional(Box::new(Accessor::IndexFromEnd(1))),
+			]),
+		}));
+
+		// A None namespace encodes as a zero-length string.
+		assert_round_trip(Value::Reference(Reference {
+			ref_type: ReferenceType::Local,
+			namespace: None,
+			variable: Arc::new("local".to_string()),
+			accessors: smallvec_accessors(vec![Accessor::RangeFull]),
+		}));
+	}
+
+	#[test]
+	fn round_trips_expr() {
+		assert_round_trip(Value::Expr(Box::new(Expr::Binary {
+			op: BinaryOp::Add,
+			lhs: Value::Number(Number::Integer(2)),
+			rhs: Value::Number(Number::Integer(3)),
+		})));
+		assert_round_trip(Value::Expr(Box::new(Expr::Unary {
+			op: UnaryOp::Neg,
+			operand: Value::Number(Number::Float(1.5)),
+		})));
+	}
+
+	#[test]
+	fn runtime_binary_file_round_trips() {
+		let mut runtime = Runtime::new();
+		runtime.namespaces.insert(Arc::new("b_ns".to_string()), {
+			let mut map = FnvHashMap::default();
+			map.insert(
+				Arc::new("var".to_string()),
+				Arc::new(Value::Number(Number::Hexadecimal(0x1F))),
+			);
+			map
+		});
+		runtime.namespaces.insert(Arc::new("a_ns".to_string()), {
+			let mut map = FnvHashMap::default();
+			map.insert(
+				Arc::new("greeting".to_string()),
+				Arc::new(Value::String("hi".to_string())),
+			);
+			map
+		});
+
+		let dir = tempdir().unwrap();
+		let path = dir.path().join("state.vtcb");
+		runtime.dump_binary_to_file(&path).unwrap();
+		let loaded = Runtime::load_binary(&path).unwrap();
+
+		assert_eq!(
+			*loaded
+				.get_value("a_ns", "greeting", &[])
+				.unwrap(),
+			Value::String("hi".to_string()),
+		);
+		assert_eq!(
+			*loaded.get_value("b_ns", "var", &[]).unwrap(),
+			Value::Number(Number::Hexadecimal(0x1F)),
+		);
+	}
+
+	fn smallvec_accessors(accessors: Vec<Accessor>) -> smallvec::SmallVec<[Accessor; vtc::SMALL_VEC_SIZE]> {
+		smallvec::SmallVec::from_vec(accessors)
+	}
+}
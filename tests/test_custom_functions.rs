@@ -1,13 +1,14 @@
 #[cfg(test)]
 mod tests {
 	use std::sync::Arc;
+	use vtc::runtime::error::RuntimeError;
 	use vtc::runtime::std::{StdLibLoader, VtcFn};
 	use vtc::runtime::Runtime;
 	use vtc::value::{Number, Value};
 
-	fn multiply_and_concatenate(args: Vec<Arc<Value>>) -> Arc<Value> {
+	fn multiply_and_concatenate(args: Vec<Arc<Value>>) -> Result<Arc<Value>, RuntimeError> {
 		if args.len() != 2 {
-			return Arc::new(Value::String("Error: Expected 2 arguments".to_string()));
+			return Ok(Arc::new(Value::String("Error: Expected 2 arguments".to_string())));
 		}
 
 		let num1 = match &*args[0] {
@@ -26,10 +27,10 @@ mod tests {
 			(Ok(n1), Ok(n2)) => {
 				let result = n1 * n2;
 				let result_string = format!("testing_{}", result);
-				Arc::new(Value::String(result_string))
+				Ok(Arc::new(Value::String(result_string)))
 			},
 			(Err(e), _) | (_, Err(e)) => {
-				Arc::new(Value::String(format!("Error: {}", e)))
+				Ok(Arc::new(Value::String(format!("Error: {}", e))))
 			}
 		}
 	}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use fnv::FnvHashMap;
+	use serde::Deserialize;
+	use vtc::runtime::Runtime;
+	use vtc::value::{Number, Reference, ReferenceType, Value};
+
+	fn int(i: i64) -> Value {
+		Value::Number(Number::Integer(i))
+	}
+
+	fn runtime_with(namespace: &str, vars: Vec<(&str, Value)>) -> Runtime {
+		let mut runtime = Runtime::new();
+		let mut map = FnvHashMap::default();
+		for (name, value) in vars {
+			map.insert(Arc::new(name.to_string()), Arc::new(value));
+		}
+		runtime.namespaces.insert(Arc::new(namespace.to_string()), map);
+		runtime
+	}
+
+	#[derive(Debug, Deserialize, PartialEq)]
+	struct Server {
+		host: String,
+		port: i64,
+		tls: bool,
+	}
+
+	#[test]
+	fn deserializes_a_struct() {
+		let runtime = runtime_with(
+			"server",
+			vec![
+				("host", Value::String("localhost".to_string())),
+				("port", int(8080)),
+				("tls", Value::Boolean(true)),
+			],
+		);
+		let cfg: Server = runtime.deserialize_namespace("server").unwrap();
+		assert_eq!(
+			cfg,
+			Server {
+				host: "localhost".to_string(),
+				port: 8080,
+				tls: true,
+			}
+		);
+	}
+
+	#[test]
+	fn deserializes_a_list_into_a_vec() {
+		#[derive(Debug, Deserialize, PartialEq)]
+		struct Cfg {
+			ports: Vec<i64>,
+		}
+		let runtime = runtime_with(
+			"net",
+			vec![("ports", Value::List(Arc::new(vec![int(1), int(2), int(3)])))],
+		);
+		let cfg: Cfg = runtime.deserialize_namespace("net").unwrap();
+		assert_eq!(cfg.ports, vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn nil_deserializes_to_none() {
+		#[derive(Debug, Deserialize, PartialEq)]
+		struct Cfg {
+			maybe: Option<i64>,
+		}
+		let runtime = runtime_with("opt", vec![("maybe", Value::Nil)]);
+		let cfg: Cfg = runtime.deserialize_namespace("opt").unwrap();
+		assert_eq!(cfg.maybe, None);
+	}
+
+	#[test]
+	fn references_are_resolved_transparently() {
+		let mut runtime = runtime_with("server", vec![("port", int(9000))]);
+		runtime.add_value(
+			"server",
+			"alias",
+			Value::Reference(Reference {
+				ref_type: ReferenceType::Local,
+				namespace: Some(Arc::new("server".to_string())),
+				variable: Arc::new("port".to_string()),
+				accessors: Default::default(),
+			}),
+		)
+		.unwrap();
+
+		#[derive(Debug, Deserialize, PartialEq)]
+		struct Cfg {
+			port: i64,
+			alias: i64,
+		}
+		let cfg: Cfg = runtime.deserialize_namespace("server").unwrap();
+		assert_eq!(cfg.port, 9000);
+		assert_eq!(cfg.alias, 9000);
+	}
+
+	#[test]
+	fn inherits_fields_from_a_dotted_ancestor() {
+		let mut runtime = runtime_with("app", vec![("tls", Value::Boolean(true))]);
+		runtime.namespaces.insert(
+			Arc::new("app.db".to_string()),
+			FnvHashMap::from_iter([(Arc::new("host".to_string()), Arc::new(Value::String("localhost".to_string())))]),
+		);
+
+		#[derive(Debug, Deserialize, PartialEq)]
+		struct Cfg {
+			host: String,
+			tls: bool,
+		}
+		let cfg: Cfg = runtime.deserialize_namespace("app.db").unwrap();
+		assert_eq!(
+			cfg,
+			Cfg {
+				host: "localhost".to_string(),
+				tls: true,
+			}
+		);
+	}
+}
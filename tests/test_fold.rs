@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use vtc::optimize::fold;
+	use vtc::value::{BinaryOp, Expr, Number, Reference, ReferenceType, Value};
+
+	fn int(i: i64) -> Value {
+		Value::Number(Number::Integer(i))
+	}
+
+	fn binary(op: BinaryOp, lhs: Value, rhs: Value) -> Value {
+		Value::Expr(Box::new(Expr::Binary { op, lhs, rhs }))
+	}
+
+	fn local_ref(name: &str) -> Value {
+		Value::Reference(Reference {
+			ref_type: ReferenceType::Local,
+			namespace: None,
+			variable: Arc::new(name.to_string()),
+			accessors: Default::default(),
+		})
+	}
+
+	#[test]
+	fn folds_constant_arithmetic() {
+		// (2 + 3) * 4 - 1 == 19
+		let expr = binary(
+			BinaryOp::Sub,
+			binary(BinaryOp::Mul, binary(BinaryOp::Add, int(2), int(3)), int(4)),
+			int(1),
+		);
+		assert_eq!(fold(expr), int(19));
+	}
+
+	#[test]
+	fn promotes_to_float_when_either_operand_is_a_float() {
+		let expr = binary(BinaryOp::Add, int(1), Value::Number(Number::Float(0.5)));
+		assert_eq!(fold(expr), Value::Number(Number::Float(1.5)));
+	}
+
+	#[test]
+	fn simplifies_additive_and_multiplicative_identities_on_non_constant_operands() {
+		let x = local_ref("x");
+
+		assert_eq!(fold(binary(BinaryOp::Add, x.clone(), int(0))), x);
+		assert_eq!(fold(binary(BinaryOp::Add, int(0), x.clone())), x);
+		assert_eq!(fold(binary(BinaryOp::Mul, x.clone(), int(1))), x);
+		assert_eq!(fold(binary(BinaryOp::Mul, int(1), x.clone())), x);
+		assert_eq!(fold(binary(BinaryOp::Mul, x.clone(), int(0))), int(0));
+		assert_eq!(fold(binary(BinaryOp::Mul, int(0), x.clone())), int(0));
+	}
+
+	#[test]
+	fn simplifies_self_subtraction() {
+		let x = local_ref("x");
+		assert_eq!(fold(binary(BinaryOp::Sub, x.clone(), x)), int(0));
+	}
+
+	#[test]
+	fn leaves_references_unevaluated() {
+		let expr = binary(BinaryOp::Add, local_ref("x"), local_ref("y"));
+		assert_eq!(fold(expr.clone()), expr);
+	}
+
+	#[test]
+	fn recurses_into_lists() {
+		let list = Value::List(Arc::new(vec![binary(BinaryOp::Add, int(1), int(2)), int(0)]));
+		assert_eq!(fold(list), Value::List(Arc::new(vec![int(3), int(0)])));
+	}
+
+	#[test]
+	fn folding_is_idempotent() {
+		let cases = vec![
+			binary(BinaryOp::Add, binary(BinaryOp::Mul, int(2), int(3)), local_ref("x")),
+			binary(BinaryOp::Sub, local_ref("x"), local_ref("x")),
+			Value::List(Arc::new(vec![binary(BinaryOp::Mul, int(4), int(0)), local_ref("y")])),
+		];
+
+		for value in cases {
+			let once = fold(value.clone());
+			let twice = fold(once.clone());
+			assert_eq!(once, twice, "fold(fold(v)) != fold(v) for {:?}", value);
+		}
+	}
+}
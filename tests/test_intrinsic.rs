@@ -47,6 +47,136 @@ mod tests {
 		assert_eq!(result, 20);
 	}
 
+	#[test]
+	fn test_add_int_promotes_on_overflow() {
+		let rt = Runtime::new();
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_add_int".to_string()),
+			Value::Number(Number::Integer(i64::MAX)),
+			Value::Number(Number::Integer(1)),
+		])));
+		let result = rt.resolve_intrinsics(expr, &mut FnvHashSet::default()).unwrap();
+		// i64::MAX + 1 does not fit in an i64, so the result promotes to BigInt.
+		match &*result {
+			Value::Number(Number::BigInt(_)) => {}
+			other => panic!("expected BigInt, got {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_add_int_demotes_back_to_integer() {
+		let rt = Runtime::new();
+		// (i64::MAX + 1) - 1 overflows then fits again, so it demotes to Integer.
+		let promoted = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_add_int".to_string()),
+			Value::Number(Number::Integer(i64::MAX)),
+			Value::Number(Number::Integer(1)),
+		])));
+		let promoted = rt.resolve_intrinsics(promoted, &mut FnvHashSet::default()).unwrap();
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_sub_int".to_string()),
+			(*promoted).clone(),
+			Value::Number(Number::Integer(1)),
+		])));
+		let result = rt.resolve_intrinsics(expr, &mut FnvHashSet::default()).unwrap();
+		assert_eq!(*result, Value::Number(Number::Integer(i64::MAX)));
+	}
+
+	#[test]
+	fn test_coercing_add_promotes_to_float() {
+		let rt = Runtime::new();
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_add".to_string()),
+			Value::Number(Number::Integer(2)),
+			Value::Number(Number::Float(0.5)),
+		])));
+		let result = rt.resolve_intrinsics(expr, &mut FnvHashSet::default()).unwrap();
+		assert_eq!(*result, Value::Number(Number::Float(2.5)));
+	}
+
+	#[test]
+	fn test_coercing_lt_across_types() {
+		let rt = Runtime::new();
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_lt".to_string()),
+			Value::Number(Number::Integer(3)),
+			Value::Number(Number::Float(3.5)),
+		])));
+		let result = rt.resolve_intrinsics(expr, &mut FnvHashSet::default()).unwrap();
+		assert_eq!(*result, Value::Boolean(true));
+	}
+
+	#[test]
+	fn test_math_sqrt() {
+		let rt = Runtime::new();
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_sqrt".to_string()),
+			Value::Number(Number::Integer(9)),
+		])));
+		let result = rt.resolve_intrinsics(expr, &mut FnvHashSet::default()).unwrap();
+		assert_eq!(*result, Value::Number(Number::Float(3.0)));
+	}
+
+	#[test]
+	fn test_math_pow_stays_integer() {
+		let rt = Runtime::new();
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_pow".to_string()),
+			Value::Number(Number::Integer(2)),
+			Value::Number(Number::Integer(10)),
+		])));
+		let result = rt.resolve_intrinsics(expr, &mut FnvHashSet::default()).unwrap();
+		assert_eq!(*result, Value::Number(Number::Integer(1024)));
+	}
+
+	#[test]
+	fn test_math_pow_large_exponent_rejected() {
+		let rt = Runtime::new();
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_pow".to_string()),
+			Value::Number(Number::Integer(2)),
+			Value::Number(Number::Integer(10_000_000)),
+		])));
+		let result = rt.resolve_intrinsics(expr, &mut FnvHashSet::default());
+		assert!(matches!(result, Err(RuntimeError::ConversionError(_))));
+	}
+
+	#[test]
+	fn test_cmp_orders_bigints_outside_i64_range() {
+		let rt = Runtime::new();
+		// Two BigInts past i64::MAX, differing only in their high limb.
+		let bigger = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_pow".to_string()),
+			Value::Number(Number::Integer(2)),
+			Value::Number(Number::Integer(100)),
+		])));
+		let bigger = rt.resolve_intrinsics(bigger, &mut FnvHashSet::default()).unwrap();
+		let smaller = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_pow".to_string()),
+			Value::Number(Number::Integer(2)),
+			Value::Number(Number::Integer(99)),
+		])));
+		let smaller = rt.resolve_intrinsics(smaller, &mut FnvHashSet::default()).unwrap();
+
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_gt".to_string()),
+			(*bigger).clone(),
+			(*smaller).clone(),
+		])));
+		let result = rt.resolve_intrinsics(expr, &mut FnvHashSet::default()).unwrap();
+		assert_eq!(*result, Value::Boolean(true));
+	}
+
+	#[test]
+	fn test_math_sqrt_negative_errors() {
+		let rt = Runtime::new();
+		let expr = Arc::new(Value::List(Arc::new(vec![
+			Value::Intrinsic("std_sqrt".to_string()),
+			Value::Number(Number::Integer(-1)),
+		])));
+		assert!(rt.resolve_intrinsics(expr, &mut FnvHashSet::default()).is_err());
+	}
+
 	#[test]
 	fn test_intrinsic_with_variable_args() {
 		let rt = setup_runtime();
@@ -63,4 +63,20 @@ mod tests {
 
 		assert_eq!(tokens, expected);
 	}
+
+	#[test]
+	fn test_lexer_big_integer() {
+		// A literal past i64::MAX falls back to the arbitrary-precision token
+		// while an in-range literal stays a plain integer.
+		let test_input = r#"
+        @testing_lexer:
+            $big := 99999999999999999999999999
+            $small := 42
+        "#;
+
+		let (_, tokens) = tokenize(test_input).unwrap();
+
+		assert!(matches!(tokens[4], Token::BigInteger(_)));
+		assert_eq!(tokens[7], Token::Integer(42));
+	}
 }
\ No newline at end of file
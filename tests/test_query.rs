@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use fnv::FnvHashMap;
+	use vtc::runtime::Runtime;
+	use vtc::value::{Number, Value};
+
+	fn int(i: i64) -> Value {
+		Value::Number(Number::Integer(i))
+	}
+
+	fn runtime_with(namespace: &str, vars: Vec<(&str, Value)>) -> Runtime {
+		let mut runtime = Runtime::new();
+		let mut map = FnvHashMap::default();
+		for (name, value) in vars {
+			map.insert(Arc::new(name.to_string()), Arc::new(value));
+		}
+		runtime.namespaces.insert(Arc::new(namespace.to_string()), map);
+		runtime
+	}
+
+	#[test]
+	fn selects_an_index() {
+		let runtime = runtime_with(
+			"app",
+			vec![("nums", Value::List(Arc::new(vec![int(1), int(2), int(3)])))],
+		);
+		let hits = runtime.select("@app.$nums->(1)").unwrap();
+		assert_eq!(hits.len(), 1);
+		assert_eq!(*hits[0], int(2));
+	}
+
+	#[test]
+	fn wildcard_expands_a_list() {
+		let runtime = runtime_with(
+			"app",
+			vec![("nums", Value::List(Arc::new(vec![int(1), int(2), int(3)])))],
+		);
+		let hits = runtime.select("@app.$nums->*").unwrap();
+		assert_eq!(hits.len(), 3);
+	}
+
+	#[test]
+	fn predicate_filters_by_number() {
+		let runtime = runtime_with(
+			"app",
+			vec![(
+				"nums",
+				Value::List(Arc::new(vec![int(5), int(20), int(7), int(42)])),
+			)],
+		);
+		let hits = runtime.select("@app.$nums->*[> 10]").unwrap();
+		let values: Vec<_> = hits.iter().map(|v| (**v).clone()).collect();
+		assert_eq!(values, vec![int(20), int(42)]);
+	}
+
+	#[test]
+	fn predicate_filters_by_type() {
+		let runtime = runtime_with(
+			"app",
+			vec![(
+				"mixed",
+				Value::List(Arc::new(vec![
+					int(1),
+					Value::String("a".to_string()),
+					Value::Number(Number::Float(2.0)),
+				])),
+			)],
+		);
+		let hits = runtime.select("@app.$mixed->*[type=string]").unwrap();
+		assert_eq!(hits.len(), 1);
+		assert_eq!(*hits[0], Value::String("a".to_string()));
+	}
+
+	#[test]
+	fn key_selects_from_a_map() {
+		let runtime = runtime_with(
+			"app",
+			vec![(
+				"server",
+				Value::Map(Arc::new(vec![
+					(Arc::new("host".to_string()), Value::String("h".to_string())),
+					(Arc::new("port".to_string()), int(80)),
+				])),
+			)],
+		);
+		let hits = runtime.select("@app.$server->[port]").unwrap();
+		assert_eq!(hits.len(), 1);
+		assert_eq!(*hits[0], int(80));
+	}
+
+	#[test]
+	fn recursive_wildcard_visits_nested_values() {
+		let runtime = runtime_with(
+			"app",
+			vec![(
+				"tree",
+				Value::List(Arc::new(vec![
+					int(1),
+					Value::List(Arc::new(vec![int(2), int(3)])),
+				])),
+			)],
+		);
+		let hits = runtime.select("@app.$tree->**[type=integer]").unwrap();
+		assert_eq!(hits.len(), 3);
+	}
+
+	#[test]
+	fn update_rewrites_matched_values() {
+		let mut runtime = runtime_with(
+			"app",
+			vec![("nums", Value::List(Arc::new(vec![int(1), int(2), int(3)])))],
+		);
+		let changed = runtime
+			.update("@app.$nums->*", |v| match v {
+				Value::Number(Number::Integer(i)) => int(i * 10),
+				other => other.clone(),
+			})
+			.unwrap();
+		assert_eq!(changed, 3);
+		let hits = runtime.select("@app.$nums->*").unwrap();
+		let values: Vec<_> = hits.iter().map(|v| (**v).clone()).collect();
+		assert_eq!(values, vec![int(10), int(20), int(30)]);
+	}
+
+	#[test]
+	fn selects_through_a_dotted_namespace() {
+		let runtime = runtime_with(
+			"app.db",
+			vec![("pool", Value::List(Arc::new(vec![int(1), int(2)])))],
+		);
+		let hits = runtime.select("@app.db.$pool->(0)").unwrap();
+		assert_eq!(hits.len(), 1);
+		assert_eq!(*hits[0], int(1));
+	}
+}
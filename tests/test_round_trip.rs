@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+	use vtc::parser::ast::to_vtc_string;
+	use vtc::parser::parse_vtc;
+
+	/// Parses `source`, renders it back with `to_vtc_string`, re-parses the
+	/// rendered text, and asserts the two `VtcFile`s are equal — the fixed
+	/// point `to_vtc_string`'s doc comment claims.
+	fn assert_round_trips(source: &str) {
+		let parsed = parse_vtc(source).expect("parse original source");
+		let rendered = to_vtc_string(&parsed);
+		let reparsed = parse_vtc(&rendered).expect("parse rendered source");
+		assert_eq!(parsed, reparsed, "re-parsed output did not match:\n{}", rendered);
+	}
+
+	#[test]
+	fn round_trips_scalars_and_lists() {
+		assert_round_trips(
+			r#"
+            @app:
+                $name := "demo"
+                $port := 8080
+                $ratio := 0.5
+                $enabled := True
+                $tags := ["a", "b", "c"]
+            "#,
+		);
+	}
+
+	#[test]
+	fn round_trips_references_with_accessors() {
+		assert_round_trips(
+			r#"
+            @app:
+                $host := "localhost"
+                $alias := %app.host
+                $first_tag := &app.tags->(0)
+            @app2:
+                $tags := ["x", "y", "z"]
+            "#,
+		);
+	}
+}
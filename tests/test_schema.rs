@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use fnv::FnvHashMap;
+	use vtc::runtime::Runtime;
+	use vtc::schema::{Schema, SchemaError};
+	use vtc::value::{Number, Reference, ReferenceType, Value};
+
+	fn runtime_with(namespace: &str, vars: Vec<(&str, Value)>) -> Runtime {
+		let mut runtime = Runtime::new();
+		let mut map = FnvHashMap::default();
+		for (name, value) in vars {
+			map.insert(Arc::new(name.to_string()), Arc::new(value));
+		}
+		runtime.namespaces.insert(Arc::new(namespace.to_string()), map);
+		runtime
+	}
+
+	#[test]
+	fn parses_the_text_format() {
+		let schema = Schema::parse(
+			"namespace app {\n\
+			 host: String\n\
+			 port: Integer\n\
+			 tags: List<String>\n\
+			 settings: Dict<String, Integer>\n\
+			 nickname: Optional<String>\n\
+			 primary: Reference<db>\n\
+			 }",
+		)
+		.unwrap();
+		assert_eq!(schema.namespaces.len(), 1);
+		assert_eq!(schema.namespaces[0].fields.len(), 6);
+	}
+
+	#[test]
+	fn accepts_a_matching_runtime() {
+		let runtime = runtime_with(
+			"app",
+			vec![
+				("host", Value::String("localhost".to_string())),
+				("port", Value::Number(Number::Integer(8080))),
+				(
+					"tags",
+					Value::List(Arc::new(vec![Value::String("a".to_string())])),
+				),
+			],
+		);
+		let schema = Schema::parse(
+			"namespace app {\n host: String\n port: Integer\n tags: List<String>\n }",
+		)
+		.unwrap();
+		assert_eq!(runtime.validate(&schema), Ok(()));
+	}
+
+	#[test]
+	fn collects_every_mismatch() {
+		let runtime = runtime_with(
+			"app",
+			vec![
+				("host", Value::Number(Number::Integer(1))),
+				(
+					"settings",
+					Value::List(Arc::new(vec![Value::String("only-key".to_string())])),
+				),
+			],
+		);
+		let schema = Schema::parse(
+			"namespace app {\n host: String\n port: Integer\n settings: Dict<String, Integer>\n }\n\
+			 namespace missing {\n whatever: Boolean\n }",
+		)
+		.unwrap();
+
+		let errors = runtime.validate(&schema).unwrap_err();
+		assert!(errors.contains(&SchemaError::TypeMismatch {
+			namespace: "app".to_string(),
+			variable: "host".to_string(),
+			expected: "String".to_string(),
+			found: "number".to_string(),
+		}));
+		assert!(errors.contains(&SchemaError::MissingVariable {
+			namespace: "app".to_string(),
+			variable: "port".to_string(),
+		}));
+		assert!(errors.contains(&SchemaError::OddLengthDict {
+			namespace: "app".to_string(),
+			variable: "settings".to_string(),
+		}));
+		assert!(errors.contains(&SchemaError::MissingNamespace("missing".to_string())));
+	}
+
+	#[test]
+	fn flags_dangling_references() {
+		let runtime = runtime_with(
+			"app",
+			vec![(
+				"primary",
+				Value::Reference(Reference {
+					ref_type: ReferenceType::External,
+					namespace: Some(Arc::new("db".to_string())),
+					variable: Arc::new("absent".to_string()),
+					accessors: smallvec::SmallVec::new(),
+				}),
+			)],
+		);
+		let schema = Schema::parse("namespace app {\n primary: Reference<db>\n }").unwrap();
+		let errors = runtime.validate(&schema).unwrap_err();
+		assert!(errors
+			.iter()
+			.any(|e| matches!(e, SchemaError::DanglingReference { .. })));
+	}
+
+	#[test]
+	fn optional_absorbs_missing_variables() {
+		let runtime = runtime_with("app", vec![]);
+		let schema = Schema::parse("namespace app {\n nickname: Optional<String>\n }").unwrap();
+		assert_eq!(runtime.validate(&schema), Ok(()));
+	}
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+	use std::io::Write;
+
+	use std::sync::Arc;
+
+	use tempfile::NamedTempFile;
+	use vtc::serializer::parser::RParser;
+	use vtc::serializer::token::{Lit, LitKind, TokenKind, Tokens};
+	use vtc::value::{Namespace, Number, Value, Variable, VtcFile};
+
+	/// Writes `source` to a temp file and runs it through `Tokens::tokenize`,
+	/// the entry point `serializer::token` exposes through the crate's public
+	/// API (`Tokens::new` only reads from a path, not a string).
+	fn tokenize(source: &str) -> Tokens {
+		let mut file = NamedTempFile::new().expect("create temp file");
+		write!(file, "{}", source).expect("write temp file");
+		let mut tokens = Tokens::new(file.path().to_str().unwrap()).expect("open temp file");
+		tokens.tokenize().expect("tokenize");
+		tokens
+	}
+
+	#[test]
+	fn tokenizes_a_container_header() {
+		let tokens = tokenize("@app:\n");
+		assert_eq!(tokens.tokens()[0], TokenKind::At);
+		assert_eq!(tokens.tokens()[1], TokenKind::Literal(Lit::new(LitKind::String, "app".to_string())));
+		assert_eq!(tokens.tokens()[2], TokenKind::Col);
+	}
+
+	#[test]
+	fn classifies_numeric_literal_kinds() {
+		let tokens = tokenize("42 3.14 0x1A 0b101\n");
+		assert_eq!(tokens.tokens()[0], TokenKind::Literal(Lit::new(LitKind::Int, "42".to_string())));
+		assert_eq!(tokens.tokens()[1], TokenKind::Literal(Lit::new(LitKind::Float, "3.14".to_string())));
+		assert_eq!(tokens.tokens()[2], TokenKind::Literal(Lit::new(LitKind::Hex, "0x1A".to_string())));
+		assert_eq!(tokens.tokens()[3], TokenKind::Literal(Lit::new(LitKind::Bin, "0b101".to_string())));
+	}
+
+	/// Two malformed numeric literals in one file should both be reported —
+	/// `Tokens::tokenize` recovers after each error and keeps scanning rather
+	/// than stopping at the first problem.
+	#[test]
+	fn accumulates_a_diagnostic_per_malformed_literal() {
+		let mut file = NamedTempFile::new().expect("create temp file");
+		write!(file, "0x 0b2\n").expect("write temp file");
+		let mut tokens = Tokens::new(file.path().to_str().unwrap()).expect("open temp file");
+
+		let diagnostics = tokens.tokenize().expect_err("both literals are malformed");
+		assert_eq!(diagnostics.len(), 2);
+		assert!(diagnostics[0].message.contains("hexadecimal"));
+		assert!(diagnostics[1].message.contains("binary"));
+	}
+
+	/// End to end: `RParser::parse` turns a whole tokenized file into the same
+	/// `VtcFile` AST the rest of the crate works with.
+	#[test]
+	fn parses_a_container_into_a_vtc_file() {
+		let tokens = tokenize(
+			"@app:\n\
+			 $name := \"demo\"\n\
+			 $port := 42\n\
+			 $tags := [1, 2]\n",
+		);
+
+		let file = RParser::new(tokens).parse().expect("parse tokens");
+		assert_eq!(
+			file,
+			VtcFile {
+				namespaces: vec![Namespace {
+					name: "app".to_string(),
+					variables: vec![
+						Variable { name: "name".to_string(), value: Value::String("demo".to_string()) },
+						Variable { name: "port".to_string(), value: Value::Number(Number::Integer(42)) },
+						Variable {
+							name: "tags".to_string(),
+							value: Value::List(Arc::new(vec![
+								Value::Number(Number::Integer(1)),
+								Value::Number(Number::Integer(2)),
+							])),
+						},
+					],
+				}],
+			}
+		);
+	}
+}